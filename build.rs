@@ -0,0 +1,60 @@
+use std::fmt::Write;
+use std::path::Path;
+
+/// With the `embedded-inputs` feature, build every `src/yYYYY/dayNN.txt` input
+/// we have into the binary, so that it can be run anywhere without them.
+fn main() {
+    // `cfg(days)` for code that's only needed when at least one day is built in,
+    // rather than listing every day feature. Features for days that haven't been
+    // written yet (for any year) don't count.
+    println!("cargo::rustc-check-cfg=cfg(days)");
+    let src = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap()).join("src");
+    let written = |day: &str| {
+        std::fs::read_dir(&src).unwrap().flatten().any(|dir| dir.path().join(format!("day{day}.rs")).exists())
+    };
+    let days = std::env::vars().any(|(key, _)| {
+        key.strip_prefix("CARGO_FEATURE_DAY").is_some_and(|day| day.parse::<u8>().is_ok() && written(day))
+    });
+    if days {
+        println!("cargo:rustc-cfg=days");
+    }
+    // So that a newly added day is noticed.
+    for dir in std::fs::read_dir(&src).unwrap().flatten().filter(|dir| dir.file_name().to_string_lossy().starts_with('y')) {
+        println!("cargo:rerun-if-changed={}", dir.path().display());
+    }
+
+    let out = Path::new(&std::env::var("OUT_DIR").unwrap()).join("embedded_inputs.rs");
+    let mut inputs = Vec::new();
+
+    if std::env::var_os("CARGO_FEATURE_EMBEDDED_INPUTS").is_some() {
+        let src = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap()).join("src");
+        println!("cargo:rerun-if-changed={}", src.display());
+
+        for year_dir in std::fs::read_dir(&src).unwrap().flatten() {
+            let name = year_dir.file_name().to_string_lossy().into_owned();
+            let Some(year) = name.strip_prefix('y').and_then(|y| y.parse::<u16>().ok()) else {
+                continue
+            };
+            for file in std::fs::read_dir(year_dir.path()).unwrap().flatten() {
+                let name = file.file_name().to_string_lossy().into_owned();
+                let Some(day) = name.strip_prefix("day").and_then(|d| d.strip_suffix(".txt")).and_then(|d| d.parse::<u8>().ok()) else {
+                    continue
+                };
+                // New days start off with an empty input; there's nothing worth keeping in those.
+                if file.metadata().map(|m| m.len() > 0).unwrap_or(false) {
+                    inputs.push((year, day, file.path()));
+                }
+            }
+        }
+        inputs.sort();
+    } else {
+        println!("cargo:rerun-if-changed=build.rs");
+    }
+
+    let mut code = String::from("pub static INPUTS: &[(u16, u8, &str)] = &[\n");
+    for (year, day, path) in inputs {
+        writeln!(code, "    ({year}, {day}, include_str!({:?})),", path.display().to_string()).unwrap();
+    }
+    code.push_str("];\n");
+    std::fs::write(out, code).unwrap();
+}
@@ -0,0 +1,58 @@
+use super::Star;
+use super::params;
+use super::solution::Solution;
+use super::utils;
+use std::path::PathBuf;
+
+/// Answers we've already worked out, one file per day, star, input and version
+/// of the day's code, so that unchanged days needn't be solved again.
+pub struct Cache {
+    pub dir: PathBuf,
+}
+
+impl Cache {
+    /// The cache is opt in; it's used when `AOC_CACHE` is set (to anything but
+    /// "0"), and lives in `cache/` in the data directory.
+    pub fn from_env() -> Option<Cache> {
+        let enabled = std::env::var("AOC_CACHE").is_ok_and(|v| !v.is_empty() && v != "0");
+        enabled.then(|| Cache { dir: utils::data_dir().join("cache") })
+    }
+    fn path(&self, solution: &Solution, star: Star, input: &str) -> PathBuf {
+        // The day's params can change the answer as much as its code can.
+        let version = format!("{}\n{}", solution.source, params::for_day(solution.year, solution.day));
+        self.dir.join(format!(
+            "{}-day{:02}-star{}-{:016x}-{:016x}.txt",
+            solution.year, solution.day, star.level(), utils::hash(input.as_bytes()), utils::hash(version.as_bytes())
+        ))
+    }
+    pub fn get(&self, solution: &Solution, star: Star, input: &str) -> Option<String> {
+        std::fs::read_to_string(self.path(solution, star, input)).ok()
+    }
+    pub fn put(&self, solution: &Solution, star: Star, input: &str, answer: &str) -> Result<(), anyhow::Error> {
+        std::fs::create_dir_all(&self.dir)?;
+        std::fs::write(self.path(solution, star, input), answer)?;
+        Ok(())
+    }
+}
+
+// Uses day 1 to have something to run.
+#[cfg(all(test, feature = "day01"))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn keyed_by_input_and_source() {
+        let dir = utils::TempDir::new("cache");
+        let cache = Cache { dir: dir.path.clone() };
+        let solution = crate::solution::find(2022, 1).unwrap();
+
+        assert_eq!(cache.get(&solution, Star::One, "1\n\n2\n"), None);
+        cache.put(&solution, Star::One, "1\n\n2\n", "2").unwrap();
+        assert_eq!(cache.get(&solution, Star::One, "1\n\n2\n").as_deref(), Some("2"));
+        assert_eq!(cache.get(&solution, Star::Two, "1\n\n2\n"), None);
+        assert_eq!(cache.get(&solution, Star::One, "1\n\n3\n"), None);
+
+        let changed = Solution { source: "fn star1() {}", ..solution };
+        assert_eq!(cache.get(&changed, Star::One, "1\n\n2\n"), None);
+    }
+}
@@ -0,0 +1,244 @@
+use super::{ File, Star };
+use super::solution::{ self, RunFn, Solution };
+use std::fmt;
+use std::ops::Range;
+use std::panic::{ self, AssertUnwindSafe };
+
+/// Params that `check` runs with unless they're given, keeping the references
+/// quick: day 15's scans its whole search area, which is millions of rows by default.
+pub const DEFAULT_PARAMS: &[&str] = &["2022.day15.row=10", "2022.day15.max=100"];
+
+/// An input on which a star and its reference implementation disagree.
+#[derive(Debug)]
+pub struct Mismatch {
+    pub year: u16,
+    pub day: u8,
+    pub star: Star,
+    /// The seed of the generated input that first disagreed.
+    pub seed: u64,
+    /// That input, shrunk down as far as it'll go while still disagreeing.
+    pub input: String,
+    pub answer: Result<String, String>,
+    pub reference: Result<String, String>,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Day {} star {} disagrees with its reference (seed {})", self.day, self.star.level(), self.seed)?;
+        writeln!(f, "  answer:    {}", show(&self.answer))?;
+        writeln!(f, "  reference: {}", show(&self.reference))?;
+        writeln!(f, "  smallest input that disagrees:")?;
+        for line in self.input.lines() {
+            writeln!(f, "    {line}")?;
+        }
+        Ok(())
+    }
+}
+
+fn show(answer: &Result<String, String>) -> String {
+    match answer {
+        Ok(answer) => answer.clone(),
+        Err(e) => format!("Error: {e}"),
+    }
+}
+
+/// Run a star and its reference on an input generated from each seed, and if
+/// they ever disagree, shrink that input down to something small that still does.
+pub fn check(solution: &Solution, star: Star, seeds: Range<u64>, size: usize) -> Result<Option<Mismatch>, anyhow::Error> {
+    let Some(reference) = solution.reference(star) else {
+        anyhow::bail!("{} day {} star {} has no reference to check against", solution.year, solution.day, star.level())
+    };
+    let real = match star {
+        Star::One => solution.star1,
+        Star::Two => solution.star2,
+    };
+
+    for seed in seeds {
+        let input = solution.generate(seed, size)?;
+        let (answer, expected) = (run(real, &input), run(reference, &input));
+        if agree(&answer, &expected) {
+            continue
+        }
+
+        // Only keep shrinking towards inputs that go wrong in the same way, so that
+        // we don't wander off into some other problem (like the input no longer parsing).
+        let shape = (answer.is_ok(), expected.is_ok());
+        let input = shrink(&input, |input| {
+            let (answer, expected) = (run(real, input), run(reference, input));
+            (answer.is_ok(), expected.is_ok()) == shape && !agree(&answer, &expected)
+        });
+        return Ok(Some(Mismatch {
+            year: solution.year,
+            day: solution.day,
+            star,
+            seed,
+            answer: run(real, &input),
+            reference: run(reference, &input),
+            input,
+        }))
+    }
+    Ok(None)
+}
+
+/// Check every star with a reference (or just those for one day), and say how it went.
+pub fn check_all(year: u16, day: Option<u8>, seeds: u64, size: usize) -> Result<String, anyhow::Error> {
+    let solutions = match day {
+        Some(day) => vec![solution::find(year, day)?],
+        None => solution::all().into_iter().filter(|s| s.year == year).collect(),
+    };
+
+    // Stars are expected to panic on some of the inputs tried while shrinking, and
+    // we report those ourselves.
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let mut out = Vec::new();
+    for solution in solutions.iter().filter(|s| s.generate.is_some()) {
+        for star in [Star::One, Star::Two].into_iter().filter(|star| solution.reference(*star).is_some()) {
+            match check(solution, star, 0..seeds, size) {
+                Ok(None) => out.push(format!("Day {} star {}: agrees on {seeds} inputs", solution.day, star.level())),
+                Ok(Some(mismatch)) => out.push(mismatch.to_string()),
+                Err(e) => out.push(format!("Day {} star {}: Error: {e}", solution.day, star.level())),
+            }
+        }
+    }
+
+    panic::set_hook(hook);
+    if out.is_empty() {
+        anyhow::bail!("Nothing to check; no days with both a reference and a generator")
+    }
+    Ok(out.join("\n"))
+}
+
+/// Run a star, counting a panic as an error.
+fn run(f: RunFn, input: &str) -> Result<String, String> {
+    let file = File { contents: input.to_owned() };
+    match panic::catch_unwind(AssertUnwindSafe(|| f(file))) {
+        Ok(answer) => answer.map_err(|e| e.to_string()),
+        Err(e) => {
+            let msg = e.downcast_ref::<&str>().map(|s| s.to_string())
+                .or_else(|| e.downcast_ref::<String>().cloned())
+                .unwrap_or_default();
+            Err(format!("panicked: {msg}"))
+        }
+    }
+}
+
+/// Both errors count as agreeing; they needn't fail with the same message.
+fn agree(a: &Result<String, String>, b: &Result<String, String>) -> bool {
+    match (a, b) {
+        (Ok(a), Ok(b)) => a == b,
+        (Err(_), Err(_)) => true,
+        _ => false,
+    }
+}
+
+/// Make an input that `fails` as small as possible while it still fails: first
+/// by taking out runs of lines (halving how many at a time down to one), and then
+/// by making the numbers in it smaller.
+fn shrink(input: &str, fails: impl Fn(&str) -> bool) -> String {
+    let trailing_newline = input.ends_with('\n');
+    let join = |lines: &[&str]| {
+        let mut s = lines.join("\n");
+        if trailing_newline && !lines.is_empty() {
+            s.push('\n');
+        }
+        s
+    };
+
+    let mut lines: Vec<&str> = input.lines().collect();
+    let mut chunk = (lines.len() / 2).max(1);
+    loop {
+        let mut removed = false;
+        let mut i = 0;
+        while i < lines.len() {
+            let candidate: Vec<&str> = lines[..i].iter().chain(lines[(i + chunk).min(lines.len())..].iter()).copied().collect();
+            if fails(&join(&candidate)) {
+                lines = candidate;
+                removed = true;
+            } else {
+                i += chunk;
+            }
+        }
+        if !removed {
+            if chunk == 1 {
+                break
+            }
+            chunk /= 2;
+        }
+    }
+
+    let mut input = join(&lines);
+    let number = regex!("-?[0-9]+");
+    loop {
+        let smaller = number.find_iter(&input).find_map(|m| {
+            let n: i64 = m.as_str().parse().ok()?;
+            [0, n / 2, n - n.signum()]
+                .into_iter()
+                .filter(|s| s.unsigned_abs() < n.unsigned_abs())
+                .map(|s| format!("{}{s}{}", &input[..m.start()], &input[m.end()..]))
+                .find(|candidate| fails(candidate))
+        });
+        match smaller {
+            Some(smaller) => input = smaller,
+            None => break input,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn shrinks_to_what_matters() {
+        // Fails whenever there's a number of at least 10 after a line saying "go".
+        let fails = |input: &str| {
+            let lines: Vec<&str> = input.lines().collect();
+            lines.iter().position(|l| *l == "go").is_some_and(|go| {
+                lines[go..].iter().any(|l| l.parse::<i64>().is_ok_and(|n| n >= 10))
+            })
+        };
+        let input = "1\n2\ngo\n3\n400\n5\n66\n7\n";
+        assert!(fails(input));
+        assert_eq!(shrink(input, fails), "go\n10\n");
+    }
+
+    #[test]
+    fn shrinks_the_most_negative_number() {
+        let fails = |input: &str| input.trim().parse::<i64>().is_ok_and(|n| n < -5);
+        assert_eq!(shrink("-9223372036854775808\n", fails), "-6\n");
+    }
+
+    #[test]
+    fn finds_mismatches() {
+        // Counts the lines, but gets it wrong when any of them is "7".
+        let solution = Solution {
+            year: 2022,
+            day: 1,
+            title: "Line Counting",
+            description: "",
+            input: true,
+            star1: |file| Ok(file.contents.lines().filter(|l| *l != "7").count().to_string()),
+            star2: |_| Ok(String::new()),
+            reference1: Some(|file| Ok(file.contents.lines().count().to_string())),
+            reference2: None,
+            stream1: None,
+            stream2: None,
+            parse: None,
+            visualise: None,
+            generate: Some(|rng, size| (0..size).map(|_| format!("{}\n", rng.range(0, 9))).collect()),
+            extras: &[],
+            source: "",
+        };
+
+        let mismatch = check(&solution, Star::One, 0..10, 20).unwrap().unwrap();
+        assert_eq!(mismatch.input, "7\n");
+        assert_eq!(mismatch.answer, Ok("0".to_owned()));
+        assert_eq!(mismatch.reference, Ok("1".to_owned()));
+
+        let solution = Solution { star1: solution.reference1.unwrap(), ..solution };
+        assert!(check(&solution, Star::One, 0..10, 20).unwrap().is_none());
+        assert!(check(&solution, Star::Two, 0..10, 20).is_err());
+    }
+}
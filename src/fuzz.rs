@@ -0,0 +1,120 @@
+use super::{ File, Star };
+use super::params;
+use super::rng::Rng;
+use super::solution;
+use std::sync::Once;
+
+/// Keep the searches that scale with a param small, so that fuzzing spends its
+/// time on odd inputs rather than on big ones.
+const PARAMS: &[&str] = &["day15.row=10", "day15.max=100", "day16.minutes=10"];
+
+/// Give an input to everything a day does with one: parse it, and then solve
+/// each star. Errors are fine, but any panic is a bug. Used by the fuzz targets
+/// in `fuzz/`.
+pub fn run(year: u16, day: u8, input: &str) {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        let params: Vec<String> = PARAMS.iter().map(|p| p.to_string()).collect();
+        params::init(year, &params).expect("fuzzing params should be valid");
+    });
+
+    let Ok(solution) = solution::find(year, day) else { return };
+    if let Some(parse) = solution.parse {
+        let _ = parse(input);
+    }
+    for star in [Star::One, Star::Two] {
+        let _ = solution.run(star, File { contents: input.to_owned() });
+    }
+}
+
+/// Numbers right at the edges of what the usual integer types hold, which is
+/// where sums and products overflow.
+const HUGE: &[&str] = &[
+    "18446744073709551615", "18446744073709551614", "9223372036854775807", "-9223372036854775808",
+    "4294967295", "2147483647", "-2147483648", "65535",
+];
+
+/// Mess an input up a little, in the sorts of ways a fuzzer would: cutting it
+/// short, dropping, repeating or swapping lines, changing characters, and
+/// making numbers enormous.
+pub fn mutate(rng: &mut Rng, input: &str) -> String {
+    const JUNK: &[char] = &['0', '1', '9', '-', ',', ' ', '\n', '[', ']', 'a', 'z', 'Z', ':', '=', '$', 'é'];
+
+    let number_re = regex!("[0-9]+");
+    let mut chars: Vec<char> = input.chars().collect();
+    for _ in 0..rng.range(1, 3) {
+        if chars.is_empty() {
+            chars.push(*rng.pick(JUNK));
+            continue
+        }
+        let at = rng.below(chars.len());
+        match rng.below(7) {
+            0 => chars.truncate(at),
+            1 => chars[at] = *rng.pick(JUNK),
+            2 => chars.insert(at, *rng.pick(JUNK)),
+            3 => chars[at] = chars[rng.below(chars.len())],
+            4 => {
+                let text: String = chars.iter().collect();
+                let numbers: Vec<_> = number_re.find_iter(&text).collect();
+                if numbers.is_empty() {
+                    continue
+                }
+                let number = numbers[rng.below(numbers.len())];
+                // Replace any sign too, so that negative numbers don't end up with two.
+                let start = if text[..number.start()].ends_with('-') { number.start() - 1 } else { number.start() };
+                chars = format!("{}{}{}", &text[..start], rng.pick(HUGE), &text[number.end()..]).chars().collect();
+            },
+            _ => {
+                let text: String = chars.iter().collect();
+                let mut lines: Vec<&str> = text.lines().collect();
+                let (a, b) = (rng.below(lines.len().max(1)), rng.below(lines.len().max(1)));
+                match lines.len() {
+                    0 => {},
+                    _ if rng.chance(0.5) => lines.swap(a, b),
+                    _ if rng.chance(0.5) => { lines.remove(a); },
+                    _ => lines.insert(a, lines[b]),
+                }
+                chars = (lines.join("\n") + "\n").chars().collect();
+            }
+        }
+    }
+    chars.into_iter().collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::panic;
+
+    #[test]
+    fn odd_inputs_dont_panic() {
+        let tiny = ["", "\n", "\n\n\n", "x", "-", "1", "0 0", "é", "[]", "$ cd ..", "move 1 from 0 to 9", "18446744073709551615\n1\n"];
+        params::with(&[("2022.day15.row", "10"), ("2022.day15.max", "100"), ("2022.day16.minutes", "10")], || {
+            let mut rng = Rng::new(0);
+            let mut panicked = Vec::new();
+            for solution in solution::all().into_iter().filter(|s| s.input) {
+                let mut inputs: Vec<String> = tiny.iter().map(|s| s.to_string()).collect();
+                if solution.generate.is_some() {
+                    for seed in 0..100 {
+                        let size = if solution.day == 16 { 4 } else { 8 };
+                        inputs.push(mutate(&mut rng, &solution.generate(seed, size).unwrap()));
+                    }
+                }
+                for input in inputs {
+                    for star in [Star::One, Star::Two] {
+                        let file = File { contents: input.clone() };
+                        if panic::catch_unwind(|| solution.run(star, file)).is_err() {
+                            panicked.push(format!("day {} star {} on:\n{input}", solution.day, star.level()));
+                        }
+                    }
+                    if let Some(parse) = solution.parse {
+                        if panic::catch_unwind(|| parse(&input)).is_err() {
+                            panicked.push(format!("day {} parse on:\n{input}", solution.day));
+                        }
+                    }
+                }
+            }
+            assert!(panicked.is_empty(), "{} panics, including {}", panicked.len(), panicked[..panicked.len().min(3)].join("\n"));
+        });
+    }
+}
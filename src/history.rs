@@ -0,0 +1,405 @@
+use super::{ File, Star };
+use super::params;
+use super::solution::Solution;
+#[cfg(feature = "bench")]
+use super::{ input, solution };
+use super::utils;
+use serde::{ Deserialize, Serialize };
+use std::io::Write;
+use std::path::PathBuf;
+#[cfg(feature = "bench")]
+use std::path::Path;
+use std::time::Duration;
+#[cfg(feature = "bench")]
+use std::time::Instant;
+
+/// One day/star being solved, as kept in the history file.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Record {
+    pub at: u64,
+    pub year: u16,
+    pub day: u8,
+    pub star: u8,
+    /// The git commit we were built from, with "-dirty" if there were local changes.
+    pub rev: String,
+    pub input_hash: String,
+    /// The day's params, one `key=value` a line, as they can change the answer too.
+    #[serde(default)]
+    pub params: String,
+    pub answer: Result<String, String>,
+    /// How many runs were asked for (fewer are made if one fails), and the
+    /// quickest and average of those made.
+    pub runs: u32,
+    pub best_ns: u64,
+    pub mean_ns: u64,
+}
+
+#[cfg(feature = "bench")]
+impl Record {
+    fn best(&self) -> Duration {
+        Duration::from_nanos(self.best_ns)
+    }
+}
+
+/// Every run we've done, one JSON record per line.
+pub struct History {
+    pub path: PathBuf,
+}
+
+impl History {
+    /// `history.jsonl` in the data directory (see `AOC_DATA_DIR`).
+    pub fn from_env() -> History {
+        History { path: utils::data_dir().join("history.jsonl") }
+    }
+    pub fn load(&self) -> Result<Vec<Record>, anyhow::Error> {
+        let contents = match std::fs::read_to_string(&self.path) {
+            Ok(c) => c,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(e.into()),
+        };
+        contents
+            .lines()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty())
+            .map(|(n, l)| serde_json::from_str(l).map_err(|e| anyhow::anyhow!("{}:{}: {e}", self.path.display(), n + 1)))
+            .collect()
+    }
+    pub fn append(&self, records: &[Record]) -> Result<(), anyhow::Error> {
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut out = String::new();
+        for record in records {
+            out.push_str(&serde_json::to_string(record)?);
+            out.push('\n');
+        }
+        // One write, so that two runs at once don't interleave their lines.
+        std::fs::OpenOptions::new().create(true).append(true).open(&self.path)?.write_all(out.as_bytes())?;
+        Ok(())
+    }
+}
+
+/// Run a star `runs` times against an input and record how it went.
+pub fn measure(solution: &Solution, star: Star, file: &File, rev: &str, runs: u32) -> Record {
+    let mut times = Vec::new();
+    let mut answer = Err("Not run".to_owned());
+    for _ in 0..runs.max(1) {
+        let (res, elapsed) = solution.run_timed(star, File { contents: file.contents.clone() });
+        times.push(elapsed);
+        answer = res.map_err(|e| format!("{e}"));
+        if answer.is_err() {
+            break
+        }
+    }
+    let total: Duration = times.iter().sum();
+    Record {
+        at: utils::now(),
+        year: solution.year,
+        day: solution.day,
+        star: star.level(),
+        rev: rev.to_owned(),
+        input_hash: format!("{:016x}", utils::hash(file.contents.as_bytes())),
+        params: params::for_day(solution.year, solution.day),
+        answer,
+        runs: runs.max(1),
+        best_ns: times.iter().min().copied().unwrap_or_default().as_nanos() as u64,
+        mean_ns: (total / times.len() as u32).as_nanos() as u64,
+    }
+}
+
+/// Save some records, warning rather than failing if that doesn't work out;
+/// the answers matter more than keeping track of them.
+pub fn record(records: &[Record]) {
+    if let Err(e) = History::from_env().append(records) {
+        eprintln!("Warning: couldn't save run history: {e}");
+    }
+}
+
+/// The commit we're running, as best we can tell.
+pub fn git_rev() -> String {
+    let git = |args: &[&str]| {
+        std::process::Command::new("git")
+            .args(args)
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_owned())
+    };
+    match git(&["rev-parse", "--short", "HEAD"]) {
+        None => "unknown".to_owned(),
+        Some(rev) if git(&["status", "--porcelain", "--untracked-files=no"]).is_some_and(|s| !s.is_empty()) => format!("{rev}-dirty"),
+        Some(rev) => rev,
+    }
+}
+
+/// Run each star of a year's days (or just one day) a few times, recording
+/// the answers and timings in the history.
+#[cfg(feature = "bench")]
+pub fn bench(year: u16, day: Option<u8>, runs: u32) -> Result<String, anyhow::Error> {
+    if let Some(day) = day {
+        solution::find(year, day)?;
+    }
+    let rev = git_rev();
+    let mut out = String::new();
+    for solution in solution::all().into_iter().filter(|s| s.year == year && day.is_none_or(|d| s.day == d)) {
+        let file = match solution.load_input(None) {
+            Ok(file) => file,
+            Err(e) => {
+                out.push_str(&format!("Day {}: Error: {e}\n", solution.day));
+                continue
+            }
+        };
+        let records: Vec<Record> = [Star::One, Star::Two]
+            .into_iter()
+            .map(|star| measure(&solution, star, &file, &rev, runs))
+            .collect();
+        for r in &records {
+            out.push_str(&summary(r, file.contents.len()));
+        }
+        record(&records);
+    }
+    out.push_str(&format!("Recorded at {rev}"));
+    Ok(out)
+}
+
+/// Run each star of a day a few times against an input file, reading through
+/// it afresh each time rather than loading it first, so that inputs too big to
+/// hold in memory can be used (by days that can stream them). Nothing is
+/// recorded, as these are one-off inputs.
+#[cfg(feature = "bench")]
+pub fn bench_stream(year: u16, day: u8, path: &Path, runs: u32) -> Result<String, anyhow::Error> {
+    let solution = solution::find(year, day)?;
+    let bytes = std::fs::metadata(path).map_err(|e| anyhow::anyhow!("Couldn't read {}: {e}", path.display()))?.len();
+    let mut out = String::new();
+    for star in [Star::One, Star::Two] {
+        let mut times = Vec::new();
+        let mut answer = Err("Not run".to_owned());
+        for _ in 0..runs.max(1) {
+            let mut reader = input::open(path)?;
+            let start = Instant::now();
+            answer = solution.run_streamed(star, &mut reader).map_err(|e| format!("{e}"));
+            times.push(start.elapsed());
+            if answer.is_err() {
+                break
+            }
+        }
+        let total: Duration = times.iter().sum();
+        let r = Record {
+            at: utils::now(),
+            year,
+            day,
+            star: star.level(),
+            rev: String::new(),
+            input_hash: String::new(),
+            params: String::new(),
+            answer,
+            runs: runs.max(1),
+            best_ns: times.iter().min().copied().unwrap_or_default().as_nanos() as u64,
+            mean_ns: (total / times.len() as u32).as_nanos() as u64,
+        };
+        out.push_str(&summary(&r, bytes as usize));
+    }
+    let how = if solution.stream1.is_some() || solution.stream2.is_some() { "streamed" } else { "read in full; this day can't stream" };
+    out.push_str(&format!("{} ({how})", path.display()));
+    Ok(out)
+}
+
+/// A line about how a benchmarked star went on an input of `bytes` bytes.
+#[cfg(feature = "bench")]
+fn summary(r: &Record, bytes: usize) -> String {
+    let answer = r.answer.as_deref().map(|a| a.lines().next().unwrap_or_default()).unwrap_or("error");
+    format!(
+        "Day {} star {}: {answer} (best {:.2?}, mean {:.2?} over {}, {:.1} MB/s)\n",
+        r.day, r.star, r.best(), Duration::from_nanos(r.mean_ns), r.runs, throughput(bytes, r.best())
+    )
+}
+
+/// How many megabytes of input a second that is.
+#[cfg(feature = "bench")]
+fn throughput(bytes: usize, time: Duration) -> f64 {
+    bytes as f64 / 1e6 / time.as_secs_f64().max(1e-9)
+}
+
+/// Something that's changed between a baseline run and the latest one.
+#[cfg(feature = "bench")]
+#[derive(Debug, PartialEq)]
+pub enum Finding {
+    AnswerChanged { was: Result<String, String>, now: Result<String, String> },
+    Slower { was: Duration, now: Duration },
+}
+
+/// Compare the latest run of each day/star/input to a baseline, which is the
+/// run before it, or the latest one at `baseline_rev` if that's given. Runs
+/// against different inputs or with different params aren't comparable, and
+/// nor is the best of a few runs with a single cold one, so those are kept apart.
+#[cfg(feature = "bench")]
+pub fn compare<'a>(records: &'a [Record], threshold: f64, baseline_rev: Option<&str>) -> Vec<(&'a Record, &'a Record, Vec<Finding>)> {
+    let key = |r: &'a Record| (r.year, r.day, r.star, r.input_hash.as_str(), r.params.as_str(), r.runs);
+    let mut keys: Vec<_> = records.iter().map(key).collect();
+    keys.sort();
+    keys.dedup();
+
+    let mut out = Vec::new();
+    for k in keys {
+        let runs: Vec<&Record> = records.iter().filter(|r| key(r) == k).collect();
+        let Some((&latest, earlier)) = runs.split_last() else {
+            continue
+        };
+        let baseline = match baseline_rev {
+            Some(rev) => earlier.iter().rev().find(|r| r.rev.starts_with(rev)),
+            None => earlier.last(),
+        };
+        let Some(&baseline) = baseline else {
+            continue
+        };
+
+        let mut findings = Vec::new();
+        if latest.answer != baseline.answer {
+            findings.push(Finding::AnswerChanged { was: baseline.answer.clone(), now: latest.answer.clone() });
+        }
+        if latest.best_ns as f64 > baseline.best_ns as f64 * (1.0 + threshold / 100.0) {
+            findings.push(Finding::Slower { was: baseline.best(), now: latest.best() });
+        }
+        out.push((baseline, latest, findings));
+    }
+    out
+}
+
+/// Show how the latest runs compare to the baseline, flagging answers that
+/// changed and stars that got more than `threshold` percent slower.
+#[cfg(feature = "bench")]
+pub fn history(year: u16, day: Option<u8>, threshold: f64, baseline_rev: Option<&str>) -> Result<String, anyhow::Error> {
+    let records: Vec<Record> = History::from_env()
+        .load()?
+        .into_iter()
+        .filter(|r| r.year == year && day.is_none_or(|d| r.day == d))
+        .collect();
+    if records.is_empty() {
+        anyhow::bail!("No runs recorded for {year} yet; try the bench command");
+    }
+
+    let mut out = String::new();
+    let mut flagged = 0;
+    for (baseline, latest, findings) in compare(&records, threshold, baseline_rev) {
+        let change = (latest.best_ns as f64 / baseline.best_ns.max(1) as f64 - 1.0) * 100.0;
+        // Just the names, as they're all the same day's.
+        let params: Vec<&str> = latest.params.lines().map(|p| p.splitn(3, '.').last().unwrap_or(p)).collect();
+        let with = if params.is_empty() { String::new() } else { format!(" with {}", params.join(", ")) };
+        let best_of = if latest.runs > 1 { format!(", best of {}", latest.runs) } else { String::new() };
+        out.push_str(&format!(
+            "Day {} star {}{with}{best_of}: {:.2?} at {} (was {:.2?} at {}, {change:+.0}%)\n",
+            latest.day, latest.star, latest.best(), latest.rev, baseline.best(), baseline.rev
+        ));
+        for finding in &findings {
+            match finding {
+                Finding::AnswerChanged { was, now } => out.push_str(&format!("    ANSWER CHANGED: {} -> {}\n", show(was), show(now))),
+                Finding::Slower { .. } => out.push_str(&format!("    SLOWER by more than {threshold}%\n")),
+            }
+        }
+        flagged += findings.len();
+    }
+    if out.is_empty() {
+        out.push_str("Nothing to compare against yet; run the bench command again\n");
+    }
+    out.push_str(&format!("{flagged} problem(s) found"));
+    Ok(out)
+}
+
+#[cfg(feature = "bench")]
+fn show(answer: &Result<String, String>) -> String {
+    match answer {
+        Ok(a) => format!("{a:?}"),
+        Err(e) => format!("error ({e})"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn record(day: u8, rev: &str, answer: &str, best_ns: u64) -> Record {
+        Record {
+            at: 0,
+            year: 2022,
+            day,
+            star: 1,
+            rev: rev.to_owned(),
+            input_hash: "abc".to_owned(),
+            params: "2022.day01.top=3\n".to_owned(),
+            answer: Ok(answer.to_owned()),
+            runs: 1,
+            best_ns,
+            mean_ns: best_ns,
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "bench")]
+    fn flags_changes_and_slowdowns() {
+        let records = vec![
+            record(1, "aaa", "10", 100),
+            record(2, "aaa", "20", 100),
+            record(1, "bbb", "10", 105),
+            record(2, "bbb", "21", 200),
+            record(1, "ccc", "10", 150),
+        ];
+
+        let found = compare(&records, 10.0, None);
+        assert_eq!(found.len(), 2);
+        assert_eq!((found[0].0.rev.as_str(), found[0].1.rev.as_str()), ("bbb", "ccc"));
+        assert_eq!(found[0].2, vec![Finding::Slower { was: Duration::from_nanos(105), now: Duration::from_nanos(150) }]);
+        assert_eq!(found[1].2.len(), 2);
+
+        // Against an older baseline, day 1 has only slowed down a little.
+        let found = compare(&records, 60.0, Some("aaa"));
+        assert_eq!(found[0].0.rev, "aaa");
+        assert!(found[0].2.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "bench")]
+    fn keeps_params_apart() {
+        let records = vec![
+            record(1, "aaa", "10", 100),
+            Record { params: "2022.day01.top=5\n".to_owned(), ..record(1, "aaa", "12", 100) },
+            record(1, "bbb", "10", 100),
+        ];
+        let found = compare(&records, 10.0, None);
+        assert_eq!(found.len(), 1);
+        assert_eq!((found[0].0.rev.as_str(), found[0].1.rev.as_str()), ("aaa", "bbb"));
+        assert!(found[0].2.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "bench")]
+    fn compares_like_runs() {
+        // A single cold run after a bench isn't a slowdown.
+        let records = vec![
+            Record { runs: 10, ..record(1, "aaa", "10", 100) },
+            record(1, "bbb", "10", 300),
+            Record { runs: 10, ..record(1, "ccc", "10", 105) },
+        ];
+        let found = compare(&records, 10.0, None);
+        assert_eq!(found.len(), 1);
+        assert_eq!((found[0].0.rev.as_str(), found[0].1.rev.as_str()), ("aaa", "ccc"));
+        assert!(found[0].2.is_empty());
+    }
+
+    #[test]
+    fn reads_records_from_before_params() {
+        let line = r#"{"at":0,"year":2022,"day":1,"star":1,"rev":"aaa","input_hash":"abc","answer":{"Ok":"10"},"runs":1,"best_ns":100,"mean_ns":100}"#;
+        let record: Record = serde_json::from_str(line).unwrap();
+        assert_eq!(record.params, "");
+    }
+
+    #[test]
+    fn round_trips_records() {
+        let dir = utils::TempDir::new("history");
+        let history = History { path: dir.path.join("history.jsonl") };
+        let records = vec![record(1, "aaa", "10", 100), Record { answer: Err("oops".to_owned()), ..record(2, "aaa", "", 5) }];
+        history.append(&records[..1]).unwrap();
+        history.append(&records[1..]).unwrap();
+        assert_eq!(history.load().unwrap(), records);
+    }
+}
@@ -0,0 +1,164 @@
+use super::File;
+use std::io::{ BufRead, BufReader };
+use std::path::{ Path, PathBuf };
+
+/// Somewhere that puzzle inputs can be fetched from when they aren't cached.
+pub trait Source {
+    fn fetch(&self, year: u16, day: u8) -> Result<String, anyhow::Error>;
+}
+
+/// Fetch inputs over HTTP from `{base_url}/{year}/day/{day}/input`, sending the
+/// session token as a cookie like a logged in browser would.
+pub struct HttpSource {
+    pub base_url: String,
+    pub session: Option<String>,
+}
+
+impl HttpSource {
+    /// Configure from `AOC_BASE_URL` (defaulting to the real site) and `AOC_SESSION`.
+    pub fn from_env() -> Self {
+        HttpSource {
+            base_url: std::env::var("AOC_BASE_URL").unwrap_or_else(|_| "https://adventofcode.com".to_owned()),
+            session: std::env::var("AOC_SESSION").ok(),
+        }
+    }
+}
+
+impl Source for HttpSource {
+    fn fetch(&self, year: u16, day: u8) -> Result<String, anyhow::Error> {
+        let Some(session) = &self.session else {
+            anyhow::bail!("Set AOC_SESSION to your session token to download inputs")
+        };
+
+        let url = format!("{}/{year}/day/{day}/input", self.base_url.trim_end_matches('/'));
+        let res = ureq::get(&url)
+            .set("Cookie", &format!("session={session}"))
+            .call();
+
+        match res {
+            Ok(res) => Ok(res.into_string()?),
+            Err(ureq::Error::Status(code, res)) => {
+                let body = res.into_string().unwrap_or_default();
+                anyhow::bail!("Fetching {url} failed with status {code}: {}", body.trim())
+            },
+            Err(e) => Err(e.into())
+        }
+    }
+}
+
+/// Look up inputs in a local cache directory, falling back to some [`Source`]
+/// (and caching what it returns) if they aren't there.
+pub struct Provider<S> {
+    pub cache_dir: PathBuf,
+    pub source: S,
+}
+
+impl Provider<HttpSource> {
+    /// Cache inputs alongside the code as `src/yYYYY/dayNN.txt` (or under
+    /// `AOC_INPUT_DIR` if set), and download missing ones via [`HttpSource::from_env()`].
+    pub fn from_env() -> Self {
+        let cache_dir = std::env::var_os("AOC_INPUT_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("src"));
+
+        Provider { cache_dir, source: HttpSource::from_env() }
+    }
+}
+
+impl<S: Source> Provider<S> {
+    pub fn get(&self, year: u16, day: u8) -> Result<File, anyhow::Error> {
+        let path = self.cache_dir.join(format!("y{year}")).join(format!("day{day:02}.txt"));
+        // An empty input is one that `new-day` made as a placeholder, rather than
+        // one we've downloaded.
+        if let Ok(contents) = std::fs::read_to_string(&path) {
+            if !contents.trim().is_empty() {
+                return Ok(File { contents })
+            }
+        }
+
+        let contents = self.source.fetch(year, day)?;
+        write_atomically(&path, &contents)?;
+        Ok(File { contents })
+    }
+}
+
+/// Open an input to read through as it's needed rather than all at once, for
+/// inputs too big to want in memory. A path of "-" reads from stdin.
+pub fn open(path: &Path) -> Result<Box<dyn BufRead>, anyhow::Error> {
+    if path == Path::new("-") {
+        return Ok(Box::new(std::io::stdin().lock()))
+    }
+    let file = std::fs::File::open(path).map_err(|e| anyhow::anyhow!("Couldn't open {}: {e}", path.display()))?;
+    Ok(Box::new(BufReader::with_capacity(1 << 16, file)))
+}
+
+mod embedded {
+    include!(concat!(env!("OUT_DIR"), "/embedded_inputs.rs"));
+}
+
+/// The input for a day, if it was built into the binary (see the `embedded-inputs` feature).
+pub fn embedded(year: u16, day: u8) -> Option<&'static str> {
+    embedded::INPUTS.iter().find(|(y, d, _)| *y == year && *d == day).map(|(_, _, input)| *input)
+}
+
+/// Write to a temporary file in the same directory and then rename it into
+/// place, so that a failed download never leaves a partial input behind.
+fn write_atomically(path: &Path, contents: &str) -> Result<(), anyhow::Error> {
+    let dir = path.parent().unwrap_or(Path::new("."));
+    std::fs::create_dir_all(dir)?;
+
+    let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("input");
+    let tmp_path = dir.join(format!(".{file_name}.{}.tmp", std::process::id()));
+
+    std::fs::write(&tmp_path, contents)?;
+    if let Err(e) = std::fs::rename(&tmp_path, path) {
+        let _ = std::fs::remove_file(&tmp_path);
+        return Err(e.into())
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn fetches_and_caches_missing_input() {
+        let (addr, server) = crate::utils::serve_once("1000\n2000\n".to_owned());
+
+        let dir = crate::utils::TempDir::new("input");
+        let provider = Provider {
+            cache_dir: dir.path.clone(),
+            source: HttpSource { base_url: format!("http://{addr}"), session: Some("abc".to_owned()) }
+        };
+
+        // Fetched from the server the first time..
+        assert_eq!(provider.get(2022, 1).unwrap().contents, "1000\n2000\n");
+        let req = server.join().unwrap();
+        assert!(req.starts_with("GET /2022/day/1/input "));
+        assert!(req.to_lowercase().contains("cookie: session=abc"));
+
+        // ..and from the cache (the server is gone now) the second time.
+        assert_eq!(provider.get(2022, 1).unwrap().contents, "1000\n2000\n");
+        assert_eq!(std::fs::read_dir(dir.path.join("y2022")).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn fetches_over_empty_placeholder() {
+        let (addr, server) = crate::utils::serve_once("A Y\n".to_owned());
+
+        let dir = crate::utils::TempDir::new("input-empty");
+        let provider = Provider {
+            cache_dir: dir.path.clone(),
+            source: HttpSource { base_url: format!("http://{addr}"), session: Some("abc".to_owned()) }
+        };
+        let path = dir.path.join("y2022").join("day02.txt");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, " \n").unwrap();
+
+        // A blank file, like the one a new day starts with, doesn't count as cached.
+        assert_eq!(provider.get(2022, 2).unwrap().contents, "A Y\n");
+        assert!(server.join().unwrap().starts_with("GET /2022/day/2/input "));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "A Y\n");
+    }
+}
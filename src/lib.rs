@@ -0,0 +1,58 @@
+
+#[macro_use] mod utils;
+#[macro_use] pub mod solution;
+pub mod cache;
+pub mod differential;
+pub mod fuzz;
+pub mod history;
+pub mod input;
+pub mod new_day;
+pub mod params;
+pub mod report;
+pub mod rng;
+pub mod serve;
+pub mod submit;
+#[cfg(feature = "tui")]
+pub mod tui;
+mod y2022;
+
+use std::str::FromStr;
+
+#[derive(Copy,Clone,PartialEq,Eq,Hash,Debug)]
+pub enum Star {
+    One,
+    Two
+}
+
+impl Star {
+    pub fn level(&self) -> u8 {
+        match self {
+            Star::One => 1,
+            Star::Two => 2
+        }
+    }
+}
+
+impl FromStr for Star {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" | "one" => Ok(Self::One),
+            "2" | "two" => Ok(Self::Two),
+            _ => anyhow::bail!("Expecting '1' or '2'")
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct File {
+    pub contents: String
+}
+
+impl FromStr for File {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let contents = std::fs::read_to_string(s)?;
+        Ok(File { contents })
+    }
+}
@@ -1,172 +1,305 @@
-#[macro_use] mod utils;
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day06;
-mod day07;
-mod day08;
-mod day09;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-
+use advent_of_code::{ cache, differential, history, input, new_day, params, report, serve, solution, submit, File, Star };
+#[cfg(feature = "tui")]
+use advent_of_code::tui;
 use clap::Parser;
-use std::{str::FromStr, fmt::Display};
+use std::fmt::Display;
 
 #[derive(Parser,Debug)]
-enum Args {
-    /// Calorie Counting
-    ///
-    /// Summing groups of numbers and then summing best 3 groups.
-    Day1(Opts),
-    /// Rock Paper Scissors
-    ///
-    /// Using set moves to win.
-    Day2(Opts),
-    /// Rucksack Reorganization
+struct Args {
+    /// Which year's puzzles to work with. Defaults to the latest one we have solutions for.
+    #[clap(long, short, global = true)]
+    year: Option<u16>,
+    /// Solve everything afresh rather than using cached answers (see AOC_CACHE).
+    #[clap(long, global = true)]
+    no_cache: bool,
+    /// Set a puzzle constant, like `--param day15.row=10`, overriding aoc.toml.
+    #[clap(long = "param", global = true)]
+    params: Vec<String>,
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Parser,Debug)]
+enum Command {
+    /// Run a day
     ///
-    /// Find the duplicate letter in each half of a string (packing items into compartments),
-    /// and then in each group of 3 strings.
-    Day3(Opts),
-    /// Camp Cleanup
+    /// Solve one star of a day's puzzle and print the answer.
+    Run {
+        day: u8,
+        #[clap(flatten)]
+        opts: Opts,
+    },
+    /// Run a day on a big input
     ///
-    /// Find overlapping work schedules.
-    Day4(Opts),
-    /// Supply Stacks
+    /// Solve one star reading the input file as it goes rather than all at once,
+    /// for inputs too big to want in memory. Days that can't do that read it in
+    /// full anyway. A file of "-" reads from stdin. Not cached or recorded.
+    Stream {
+        day: u8,
+        #[clap(long, short)]
+        star: Star,
+        file: std::path::PathBuf,
+    },
+    /// Run every day
     ///
-    /// Rearranging crates stacked on top of eachother by following instructions.
-    Day5(Opts),
-    /// Tuning Trouble
+    /// Solve both stars of every day in the year using their usual inputs.
+    All,
+    /// List the days
     ///
-    /// Find first 4 or 14 non-repeating letters.
-    Day6(Opts),
-    /// No Space Left On Device
+    /// Show each day we have a solution for, along with a little about it.
+    List,
+    /// Browse the days
     ///
-    /// Parse terminal commands to worrk out file structure and sizes of things.
-    Day7(Opts),
-    /// Treetop Tree House
+    /// A terminal dashboard to run stars, see answers and timings, and look at
+    /// visualisations for the days that have them.
+    #[cfg(feature = "tui")]
+    Tui,
+    /// Write a report
     ///
-    /// Counting visible trees in a grid based on their height.
-    Day8(Opts),
-    /// Rope Bridge
+    /// Run every day and write the answers, timings and renders out as HTML and Markdown.
+    Report {
+        #[clap(long, short, default_value = "report")]
+        out: std::path::PathBuf,
+    },
+    /// Serve a report
     ///
-    /// A rope tail following a head around.
-    Day9(Opts),
-    /// Cathode-Ray Tube
+    /// Start a web server on localhost with a page per day showing answers, timings
+    /// and visualisations, and which can re-run a day against an uploaded input.
+    Serve {
+        #[clap(long, short, default_value_t = 8080)]
+        port: u16,
+    },
+    /// Benchmark the days
     ///
-    /// Basic assembly; noop & addx. Check value of X at cycles. Print a message.
-    Day10(Opts),
-    /// Monkey in the Middle
+    /// Run each star of every day (or just one) a few times, and record the answers
+    /// and timings in the run history.
+    #[cfg(feature = "bench")]
+    Bench {
+        #[clap(long, short)]
+        day: Option<u8>,
+        #[clap(long, short, default_value_t = 5)]
+        runs: u32,
+        /// Stream this input file for the day instead (see `stream`), which isn't recorded.
+        #[clap(long, requires = "day")]
+        stream: Option<std::path::PathBuf>,
+    },
+    /// Look for regressions
     ///
-    /// Monkeys throwing items to eachother with rules. Hardcoded, so no file input.
-    /// Couldn't be bothered to parse it. Liked the part 2.
-    Day11{ #[clap(short,long)] star: Star },
-    /// Hill Climbing Algorithm
+    /// Compare the latest recorded run of each star with the one before it (or the
+    /// latest at a given git revision), flagging changed answers and slowdowns.
+    #[cfg(feature = "bench")]
+    History {
+        #[clap(long, short)]
+        day: Option<u8>,
+        /// How much slower, as a percentage, counts as a slowdown.
+        #[clap(long, short, default_value_t = 25.0)]
+        threshold: f64,
+        /// Compare against the latest run at this git revision instead.
+        #[clap(long, short)]
+        baseline: Option<String>,
+    },
+    /// Run one of a day's extras
     ///
-    /// Find the shortest path(s) up a hill.
-    Day12(Opts),
-    /// Distress Signal
+    /// Days can do more with their input than solve the stars (see `list`). Options
+    /// for an extra come after its name and set the day's params, so that
+    /// `extra 1 top --top 5` is the same as `--param day01.top=5 extra 1 top`.
+    #[clap(trailing_var_arg = true)]
+    Extra {
+        day: u8,
+        /// Input file, as for `run` (given before the day, like `extra -f input.txt 1 top`).
+        #[clap(long, short)]
+        file: Option<File>,
+        /// The extra's name, and then any options for it.
+        #[clap(required = true, multiple_values = true, allow_hyphen_values = true, value_name = "NAME [OPTIONS]")]
+        extra: Vec<String>,
+    },
+    /// Generate an input
     ///
-    /// Compare pairs of items/lists of items, and then sort them.
-    Day13(Opts),
-    /// Regolith Reservoir
+    /// Make up a random (but valid) input for a day. The same seed and size always
+    /// give the same input; what the size counts depends on the day.
+    Gen {
+        day: u8,
+        #[clap(long, default_value_t = 0)]
+        seed: u64,
+        #[clap(long, default_value_t = 100)]
+        size: usize,
+    },
+    /// Check against the references
     ///
-    /// Simulate falling sand and see how much settles.
-    Day14(Opts),
-    /// Beacon Exclusion Zone
+    /// Run each star that has a slow reference implementation (or just those of one
+    /// day) alongside it on generated inputs, and show the smallest input that they
+    /// disagree on, if any.
+    Check {
+        day: Option<u8>,
+        /// How many inputs to try, seeded from 0 upwards.
+        #[clap(long, default_value_t = 100)]
+        seeds: u64,
+        #[clap(long, default_value_t = 20)]
+        size: usize,
+    },
+    /// Scaffold a new day
     ///
-    /// Sensors and beacons; which square is the actual beacon on?
-    Day15(Opts),
-    /// Proboscidea Volcanium
+    /// Generate a module and empty input file for a day and register it.
+    NewDay {
+        day: u8,
+        #[clap(long)]
+        title: String,
+        #[clap(long, default_value = "")]
+        description: String,
+    },
+    /// Submit an answer
     ///
-    /// Searching tunnels and release valves to find best order to release most pressure.
-    Day16(Opts),
+    /// Post an answer for a day and star, refusing ones already known to be wrong
+    /// and waiting out the cooldown between attempts.
+    Submit {
+        day: u8,
+        #[clap(long, short)]
+        star: Star,
+        answer: String,
+    },
 }
 
 fn main() {
-    use Args::*;
+    use Command::*;
     let args = Args::parse();
+    let year = args.year.unwrap_or_else(solution::latest_year);
+    // Given params come last, so that they win over any defaults for the command.
+    let mut param_args: Vec<String> = match &args.command {
+        Check { .. } => differential::DEFAULT_PARAMS.iter().map(|p| p.to_string()).collect(),
+        _ => Vec::new(),
+    };
+    param_args.extend(args.params.iter().cloned());
+    if let Extra { day, extra, .. } = &args.command {
+        match params::from_options(*day, &extra[1..]) {
+            Ok(options) => param_args.extend(options),
+            Err(e) => {
+                eprintln!("Error: {e:?}");
+                std::process::exit(1);
+            }
+        }
+    }
+    if let Err(e) = params::init(year, &param_args) {
+        eprintln!("Error: {e:?}");
+        std::process::exit(1);
+    }
+    let cache = cache::Cache::from_env().filter(|_| !args.no_cache);
+
+    match args.command {
+        Run { day, opts } => print(run(year, day, opts, cache.as_ref())),
+        Stream { day, star, file } => print(stream(year, day, star, &file)),
+        All => all(year, cache.as_ref()),
+        List => list(year),
+        #[cfg(feature = "tui")]
+        Tui => print(tui::run(year)),
+        Report { out } => print(report::report(&out, |s| eprintln!("Running {} day {}", s.year, s.day))),
+        Serve { port } => {
+            if let Err(e) = serve::serve(port) {
+                eprintln!("Error: {e:?}");
+            }
+        },
+        #[cfg(feature = "bench")]
+        Bench { day: Some(day), runs, stream: Some(path) } => print(history::bench_stream(year, day, &path, runs)),
+        #[cfg(feature = "bench")]
+        Bench { day, runs, .. } => print(history::bench(year, day, runs)),
+        #[cfg(feature = "bench")]
+        History { day, threshold, baseline } => print(history::history(year, day, threshold, baseline.as_deref())),
+        Extra { day, file, extra: name_and_options } => print(extra(year, day, &name_and_options[0], file)),
+        Gen { day, seed, size } => {
+            // Printed as is, since a trailing blank line means something to some days.
+            match solution::find(year, day).and_then(|s| s.generate(seed, size)) {
+                Ok(input) => print!("{input}"),
+                Err(e) => eprintln!("Error: {e:?}"),
+            }
+        },
+        Check { day, seeds, size } => print(differential::check_all(year, day, seeds, size)),
+        NewDay { day, title, description } => print(new_day::create(year, day, &title, &description)),
+        Submit { day, star, answer } => print(submit::submit(year, day, star.level(), &answer)),
+    }
+}
 
-    match args {
-        Day1(Opts { star: Star::One, file }) => print(day01::star1(file)),
-        Day1(Opts { star: Star::Two, file }) => print(day01::star2(file)),
-        Day2(Opts { star: Star::One, file }) => print(day02::star1(file)),
-        Day2(Opts { star: Star::Two, file }) => print(day02::star2(file)),
-        Day3(Opts { star: Star::One, file }) => print(day03::star1(file)),
-        Day3(Opts { star: Star::Two, file }) => print(day03::star2(file)),
-        Day4(Opts { star: Star::One, file }) => print(day04::star1(file)),
-        Day4(Opts { star: Star::Two, file }) => print(day04::star2(file)),
-        Day5(Opts { star: Star::One, file }) => print(day05::star1(file)),
-        Day5(Opts { star: Star::Two, file }) => print(day05::star2(file)),
-        Day6(Opts { star: Star::One, file }) => print(day06::star1(file)),
-        Day6(Opts { star: Star::Two, file }) => print(day06::star2(file)),
-        Day7(Opts { star: Star::One, file }) => print(day07::star1(file)),
-        Day7(Opts { star: Star::Two, file }) => print(day07::star2(file)),
-        Day8(Opts { star: Star::One, file }) => print(day08::star1(file)),
-        Day8(Opts { star: Star::Two, file }) => print(day08::star2(file)),
-        Day9(Opts { star: Star::One, file }) => print(day09::star1(file)),
-        Day9(Opts { star: Star::Two, file }) => print(day09::star2(file)),
-        Day10(Opts { star: Star::One, file }) => print(day10::star1(file)),
-        Day10(Opts { star: Star::Two, file }) => print(day10::star2(file)),
-        Day11{ star: Star::One } => print(day11::star1()),
-        Day11{ star: Star::Two } => print(day11::star2()),
-        Day12(Opts { star: Star::One, file }) => print(day12::star1(file)),
-        Day12(Opts { star: Star::Two, file }) => print(day12::star2(file)),
-        Day13(Opts { star: Star::One, file }) => print(day13::star1(file)),
-        Day13(Opts { star: Star::Two, file }) => print(day13::star2(file)),
-        Day14(Opts { star: Star::One, file }) => print(day14::star1(file)),
-        Day14(Opts { star: Star::Two, file }) => print(day14::star2(file)),
-        Day15(Opts { star: Star::One, file }) => print(day15::star1(file)),
-        Day15(Opts { star: Star::Two, file }) => print(day15::star2(file)),
-        Day16(Opts { star: Star::One, file }) => print(day16::star1(file)),
-        Day16(Opts { star: Star::Two, file }) => print(day16::star2(file)),
+fn run(year: u16, day: u8, opts: Opts, cache: Option<&cache::Cache>) -> Result<String, anyhow::Error> {
+    let solution = solution::find(year, day)?;
+    let file = solution.load_input(opts.file)?;
+    let (answer, cached) = solve(&solution, opts.star, &file, &history::git_rev(), cache);
+    if cached {
+        // Not alongside the answer, so that it can still be piped somewhere.
+        eprintln!("(cached)");
     }
+    answer.map_err(anyhow::Error::msg)
 }
 
-#[derive(Copy,Clone,PartialEq,Eq,Debug)]
-enum Star {
-    One,
-    Two
+fn stream(year: u16, day: u8, star: Star, path: &std::path::Path) -> Result<String, anyhow::Error> {
+    let solution = solution::find(year, day)?;
+    solution.run_streamed(star, &mut input::open(path)?)
 }
 
-impl FromStr for Star {
-    type Err = anyhow::Error;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "1" | "one" => Ok(Self::One),
-            "2" | "two" => Ok(Self::Two),
-            _ => anyhow::bail!("Expecting '1' or '2'")
+/// Solve a star, using a cached answer if there is one. Anything actually
+/// solved is recorded in the history, and cached for next time.
+fn solve(solution: &solution::Solution, star: Star, file: &File, rev: &str, cache: Option<&cache::Cache>) -> (Result<String, String>, bool) {
+    if let Some(answer) = cache.and_then(|c| c.get(solution, star, &file.contents)) {
+        return (Ok(answer), true)
+    }
+    let record = history::measure(solution, star, file, rev, 1);
+    history::record(std::slice::from_ref(&record));
+    if let (Some(cache), Ok(answer)) = (cache, &record.answer) {
+        if let Err(e) = cache.put(solution, star, &file.contents, answer) {
+            eprintln!("Warning: couldn't cache answer: {e}");
         }
     }
+    (record.answer, false)
 }
 
-#[derive(Parser,Debug)]
-struct Opts {
-    #[clap(long, short)]
-    file: File,
-    #[clap(long, short)]
-    star: Star,
+fn all(year: u16, cache: Option<&cache::Cache>) {
+    let rev = history::git_rev();
+    for solution in solution::all().into_iter().filter(|s| s.year == year) {
+        let file = match solution.load_input(None) {
+            Ok(file) => file,
+            Err(e) => {
+                println!("Day {}: Error: {e}", solution.day);
+                continue
+            }
+        };
+        for star in [Star::One, Star::Two] {
+            let (answer, cached) = solve(&solution, star, &file, &rev, cache);
+            let marker = if cached { " (cached)" } else { "" };
+            match answer {
+                Ok(answer) => println!("Day {} star {}: {answer}{marker}", solution.day, star.level()),
+                Err(e) => println!("Day {} star {}: Error: {e}", solution.day, star.level()),
+            }
+        }
+    }
 }
 
-#[derive(Debug)]
-pub struct File {
-    pub contents: String
+fn extra(year: u16, day: u8, name: &str, file: Option<File>) -> Result<String, anyhow::Error> {
+    let solution = solution::find(year, day)?;
+    let extra = solution.extra(name)?;
+    (extra.run)(solution.load_input(file)?)
 }
 
-impl FromStr for File {
-    type Err = anyhow::Error;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let contents = std::fs::read_to_string(s)?;
-        Ok(File { contents })
+fn list(year: u16) {
+    for solution in solution::all().into_iter().filter(|s| s.year == year) {
+        let embedded = if solution.input && input::embedded(year, solution.day).is_some() { " (input embedded)" } else { "" };
+        println!("Day {}: {}{embedded}", solution.day, solution.title);
+        if !solution.description.is_empty() {
+            println!("    {}", solution.description);
+        }
+        for extra in solution.extras {
+            println!("    extra '{}': {}", extra.name, extra.description);
+        }
     }
 }
 
+#[derive(Parser,Debug)]
+struct Opts {
+    /// Input file. If not given, the input built into the binary is used (with the
+    /// embedded-inputs feature), or else it's loaded from the cache, or downloaded
+    /// into it (see AOC_INPUT_DIR, AOC_BASE_URL and AOC_SESSION).
+    #[clap(long, short)]
+    file: Option<File>,
+    #[clap(long, short)]
+    star: Star,
+}
+
 fn print<T: Display, E: std::fmt::Debug>(val: Result<T, E>) {
     match val {
         Ok(res) => println!("{res}"),
@@ -0,0 +1,222 @@
+use std::path::Path;
+
+/// Generate `src/yYYYY/dayNN.rs` and an empty `src/yYYYY/dayNN.txt`, and register
+/// the new day (and the year, if it's a new one) so that it can be run straight away.
+pub fn create(year: u16, day: u8, title: &str, description: &str) -> Result<String, anyhow::Error> {
+    if !(1..=25).contains(&day) {
+        anyhow::bail!("Day must be between 1 and 25, not {day}");
+    }
+
+    let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    let year_dir = src.join(format!("y{year}"));
+    let year_path = year_dir.join("mod.rs");
+    let module_path = year_dir.join(format!("day{day:02}.rs"));
+    let input_path = year_dir.join(format!("day{day:02}.txt"));
+
+    if module_path.exists() {
+        anyhow::bail!("{} already exists", module_path.display());
+    }
+
+    // Work out all of the changes before writing anything.
+    let mut writes = Vec::new();
+    let year_rs = if year_path.exists() {
+        std::fs::read_to_string(&year_path)?
+    } else {
+        let lib_path = src.join("lib.rs");
+        let solution_path = src.join("solution.rs");
+        let lib_rs = register_year_mod(&std::fs::read_to_string(&lib_path)?, year)?;
+        let solution_rs = register_year_solutions(&std::fs::read_to_string(&solution_path)?, year)?;
+        writes.push((lib_path, lib_rs));
+        writes.push((solution_path, solution_rs));
+        year_template().to_owned()
+    };
+    writes.push((year_path, register_day(&year_rs, year, day, title, description)?));
+    writes.push((module_path.clone(), module_template().to_owned()));
+    if !input_path.exists() {
+        writes.push((input_path.clone(), String::new()));
+    }
+
+    std::fs::create_dir_all(&year_dir)?;
+    for (path, contents) in writes {
+        std::fs::write(path, contents)?;
+    }
+
+    Ok(format!("Created {} and {}", module_path.display(), input_path.display()))
+}
+
+fn module_template() -> &'static str {
+r#"use crate::File;
+
+pub fn star1(_file: File) -> Result<usize, anyhow::Error> {
+    anyhow::bail!("Not solved yet")
+}
+
+pub fn star2(_file: File) -> Result<usize, anyhow::Error> {
+    anyhow::bail!("Not solved yet")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[ignore]
+    fn example() {
+        let input = "
+        ";
+
+        assert_eq!(star1(File { contents: input.to_string() }).unwrap(), 0);
+    }
+}
+"#
+}
+
+fn year_template() -> &'static str {
+r#"use crate::solution::Solution;
+
+// Each day is only pushed if it's been built in (see the day features).
+#[allow(unused_mut, clippy::vec_init_then_push)]
+pub fn solutions() -> Vec<Solution> {
+    let mut solutions = Vec::new();
+    solutions
+}
+"#
+}
+
+/// Add the `mod` and the `solution!` entry for a new day to the contents of a
+/// year's `mod.rs`, each after the last existing day and behind the day's feature.
+fn register_day(year_rs: &str, year: u16, day: u8, title: &str, description: &str) -> Result<String, anyhow::Error> {
+    let module = format!("day{day:02}");
+    if year_rs.lines().any(|l| l.trim() == format!("mod {module};")) {
+        anyhow::bail!("'{module}' is already registered for {year}");
+    }
+
+    let mut lines = to_lines(year_rs);
+
+    let cfg = format!("#[cfg(feature = \"{module}\")]");
+
+    // Solution entry first (just before they're returned), so that the earlier
+    // line numbers stay valid.
+    let idx = last_line_where(&lines, |l| l == "solutions")
+        .ok_or_else(|| anyhow::anyhow!("Couldn't find where to add the solution for {year}"))?;
+    lines.insert(idx, format!("    solutions.push(solution!({year}, {day}, {module}, {title:?}, {description:?}));"));
+    lines.insert(idx, format!("    {cfg}"));
+
+    let idx = last_line_where(&lines, |l| l.starts_with("mod day")).map_or(0, |idx| idx + 1);
+    lines.insert(idx, format!("mod {module};"));
+    lines.insert(idx, cfg);
+    if idx == 0 {
+        lines.insert(2, String::new());
+    }
+
+    Ok(from_lines(lines, year_rs))
+}
+
+/// Add the `mod` for a new year to `lib.rs`.
+fn register_year_mod(lib_rs: &str, year: u16) -> Result<String, anyhow::Error> {
+    let mut lines = to_lines(lib_rs);
+    let idx = last_line_where(&lines, |l| l.starts_with("mod y"))
+        .ok_or_else(|| anyhow::anyhow!("Couldn't find where to add 'mod y{year};' in lib.rs"))?;
+    lines.insert(idx + 1, format!("mod y{year};"));
+    Ok(from_lines(lines, lib_rs))
+}
+
+/// Add a new year's solutions to the list of all of them in `solution.rs`.
+fn register_year_solutions(solution_rs: &str, year: u16) -> Result<String, anyhow::Error> {
+    let mut lines = to_lines(solution_rs);
+    let idx = last_line_where(&lines, |l| l.ends_with("::solutions());"))
+        .ok_or_else(|| anyhow::anyhow!("Couldn't find where to add the {year} solutions in solution.rs"))?;
+    lines.insert(idx + 1, format!("    solutions.extend(super::y{year}::solutions());"));
+    Ok(from_lines(lines, solution_rs))
+}
+
+fn last_line_where(lines: &[String], f: impl Fn(&str) -> bool) -> Option<usize> {
+    lines.iter().rposition(|l| f(l.trim()))
+}
+
+fn to_lines(s: &str) -> Vec<String> {
+    s.lines().map(|l| l.to_owned()).collect()
+}
+
+fn from_lines(lines: Vec<String>, original: &str) -> String {
+    let mut out = lines.join("\n");
+    if original.ends_with('\n') {
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn registers_day() {
+        let year_rs = "\
+#[cfg(feature = \"day01\")]
+mod day01;
+#[cfg(feature = \"day11\")]
+mod day11;
+
+use crate::solution::Solution;
+
+pub fn solutions() -> Vec<Solution> {
+    let mut solutions = Vec::new();
+    #[cfg(feature = \"day01\")]
+    solutions.push(solution!(2022, 1, day01, \"Calorie Counting\", \"Summing numbers.\"));
+    #[cfg(feature = \"day11\")]
+    solutions.push(Solution {
+        input: false,
+        ..solution!(2022, 11, day11, \"Monkey in the Middle\", \"Monkeys.\")
+    });
+    solutions
+}
+";
+        let expected = "\
+#[cfg(feature = \"day01\")]
+mod day01;
+#[cfg(feature = \"day11\")]
+mod day11;
+#[cfg(feature = \"day17\")]
+mod day17;
+
+use crate::solution::Solution;
+
+pub fn solutions() -> Vec<Solution> {
+    let mut solutions = Vec::new();
+    #[cfg(feature = \"day01\")]
+    solutions.push(solution!(2022, 1, day01, \"Calorie Counting\", \"Summing numbers.\"));
+    #[cfg(feature = \"day11\")]
+    solutions.push(Solution {
+        input: false,
+        ..solution!(2022, 11, day11, \"Monkey in the Middle\", \"Monkeys.\")
+    });
+    #[cfg(feature = \"day17\")]
+    solutions.push(solution!(2022, 17, day17, \"Pyroclastic \\\"Flow\\\"\", \"\"));
+    solutions
+}
+";
+        assert_eq!(register_day(year_rs, 2022, 17, "Pyroclastic \"Flow\"", "").unwrap(), expected);
+        assert!(register_day(expected, 2022, 17, "Pyroclastic Flow", "").is_err());
+    }
+
+    #[test]
+    fn registers_day_in_new_year() {
+        let expected = "\
+#[cfg(feature = \"day01\")]
+mod day01;
+
+use crate::solution::Solution;
+
+// Each day is only pushed if it's been built in (see the day features).
+#[allow(unused_mut, clippy::vec_init_then_push)]
+pub fn solutions() -> Vec<Solution> {
+    let mut solutions = Vec::new();
+    #[cfg(feature = \"day01\")]
+    solutions.push(solution!(2023, 1, day01, \"Trebuchet?!\", \"Calibration values.\"));
+    solutions
+}
+";
+        assert_eq!(register_day(year_template(), 2023, 1, "Trebuchet?!", "Calibration values.").unwrap(), expected);
+    }
+}
@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::RwLock;
+
+/// Puzzle constants that days read (like which row to look at in 2022 day 15),
+/// keyed like "2022.day15.row". Anything not set uses the puzzle's usual value.
+static PARAMS: RwLock<Option<HashMap<String, String>>> = RwLock::new(None);
+
+#[cfg(test)]
+thread_local! {
+    static OVERRIDES: std::cell::RefCell<HashMap<String, String>> = Default::default();
+}
+
+/// Load `aoc.toml` (or `AOC_CONFIG`, if set) and then apply any `key=value`
+/// params given on the command line on top. Keys without a year are for `year`.
+pub fn init(year: u16, overrides: &[String]) -> Result<(), anyhow::Error> {
+    let path = std::env::var_os("AOC_CONFIG")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|| std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("aoc.toml"));
+
+    let mut params = HashMap::new();
+    match std::fs::read_to_string(&path) {
+        Ok(contents) => {
+            let table: toml::Table = toml::from_str(&contents)
+                .map_err(|e| anyhow::anyhow!("Couldn't read {}: {e}", path.display()))?;
+            flatten("", &toml::Value::Table(table), &mut params);
+        },
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {},
+        Err(e) => return Err(e.into()),
+    }
+
+    for param in overrides {
+        let Some((key, value)) = param.split_once('=') else {
+            anyhow::bail!("Expecting a param like 'day15.row=10', but got '{param}'");
+        };
+        params.insert(qualify(year, key.trim()), value.trim().to_owned());
+    }
+
+    *PARAMS.write().unwrap() = Some(params);
+    Ok(())
+}
+
+/// Turn nested tables into dotted keys, and lists into comma separated values.
+fn flatten(prefix: &str, value: &toml::Value, out: &mut HashMap<String, String>) {
+    let value = match value {
+        toml::Value::Table(table) => {
+            for (key, value) in table {
+                let key = if prefix.is_empty() { key.clone() } else { format!("{prefix}.{key}") };
+                flatten(&key, value, out);
+            }
+            return
+        },
+        toml::Value::String(s) => s.clone(),
+        toml::Value::Array(items) => items
+            .iter()
+            .map(|i| match i {
+                toml::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(","),
+        other => other.to_string(),
+    };
+    out.insert(prefix.to_owned(), value);
+}
+
+/// Turn options like `--top 5` (or `--top=5`, or just `--json` for "true") into
+/// params for a day, like "day01.top=5".
+pub fn from_options(day: u8, options: &[String]) -> Result<Vec<String>, anyhow::Error> {
+    let mut params = Vec::new();
+    let mut options = options.iter().peekable();
+    while let Some(option) = options.next() {
+        let Some(key) = option.strip_prefix("--").filter(|k| !k.is_empty()) else {
+            anyhow::bail!("Expecting an option like '--top 5', but got '{option}'");
+        };
+        let (key, value) = match key.split_once('=') {
+            Some((key, value)) => (key, value.to_owned()),
+            None => match options.next_if(|next| !next.starts_with("--")) {
+                Some(value) => (key, value.clone()),
+                None => (key, "true".to_owned()),
+            }
+        };
+        params.push(format!("day{day:02}.{}={value}", key.replace('-', "_")));
+    }
+    Ok(params)
+}
+
+fn qualify(year: u16, key: &str) -> String {
+    if key.starts_with(|c: char| c.is_ascii_digit()) {
+        key.to_owned()
+    } else {
+        format!("{year}.{key}")
+    }
+}
+
+fn lookup(key: &str) -> Option<String> {
+    #[cfg(test)]
+    if let Some(value) = OVERRIDES.with(|o| o.borrow().get(key).cloned()) {
+        return Some(value)
+    }
+    PARAMS.read().unwrap().as_ref()?.get(key).cloned()
+}
+
+/// Read a param, falling back to `default` if it's not been set.
+pub fn get<T: FromStr>(key: &str, default: T) -> Result<T, anyhow::Error> where T::Err: Display {
+    match lookup(key) {
+        None => Ok(default),
+        Some(value) => value.parse().map_err(|e| anyhow::anyhow!("Bad value '{value}' for param {key}: {e}")),
+    }
+}
+
+/// Read a comma separated list of things.
+pub fn get_list<T: FromStr>(key: &str, default: Vec<T>) -> Result<Vec<T>, anyhow::Error> where T::Err: Display {
+    match lookup(key) {
+        None => Ok(default),
+        Some(value) => value
+            .split(',')
+            .filter(|v| !v.trim().is_empty())
+            .map(|v| v.trim().parse().map_err(|e| anyhow::anyhow!("Bad value '{v}' for param {key}: {e}")))
+            .collect(),
+    }
+}
+
+/// Every param set for a day, as "key=value" lines; these can change the answers
+/// as much as the input does.
+pub fn for_day(year: u16, day: u8) -> String {
+    let prefix = format!("{year}.day{day:02}.");
+    let params = PARAMS.read().unwrap();
+    let mut lines: Vec<String> = params
+        .iter()
+        .flatten()
+        .filter(|(k, _)| k.starts_with(&prefix))
+        .map(|(k, v)| format!("{k}={v}\n"))
+        .collect();
+    lines.sort();
+    lines.concat()
+}
+
+/// Run something with some params set, on this thread only; handy for running
+/// the examples, which tend to use different numbers from the real thing.
+#[cfg(test)]
+pub fn with<T>(params: &[(&str, &str)], f: impl FnOnce() -> T) -> T {
+    OVERRIDES.with(|o| o.borrow_mut().extend(params.iter().map(|(k, v)| (k.to_string(), v.to_string()))));
+    let res = f();
+    OVERRIDES.with(|o| o.borrow_mut().clear());
+    res
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn options_become_params() {
+        let options: Vec<String> = ["--top", "5", "--format=json", "--per-elf", "--min", "-3"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(
+            from_options(1, &options).unwrap(),
+            ["day01.top=5", "day01.format=json", "day01.per_elf=true", "day01.min=-3"]
+        );
+        assert!(from_options(1, &["5".to_owned()]).is_err());
+    }
+
+    #[test]
+    fn flattens_tables() {
+        let table: toml::Table = toml::from_str("[2022.day10]\ncycles = [20, 60]\n[2022.day16]\nstart = \"AA\"\nminutes = 30\n").unwrap();
+        let mut params = HashMap::new();
+        flatten("", &toml::Value::Table(table), &mut params);
+        assert_eq!(params["2022.day10.cycles"], "20,60");
+        assert_eq!(params["2022.day16.start"], "AA");
+        assert_eq!(params["2022.day16.minutes"], "30");
+
+        assert_eq!(qualify(2022, "day15.row"), "2022.day15.row");
+        assert_eq!(qualify(2022, "2021.day15.row"), "2021.day15.row");
+    }
+
+    #[test]
+    fn overrides() {
+        with(&[("2099.day01.n", "5"), ("2099.day01.list", "1, 2,3")], || {
+            assert_eq!(get("2099.day01.n", 1).unwrap(), 5);
+            assert_eq!(get("2099.day01.missing", 1).unwrap(), 1);
+            assert_eq!(get_list("2099.day01.list", vec![0]).unwrap(), vec![1, 2, 3]);
+            assert!(get::<u8>("2099.day01.list", 0).is_err());
+        });
+    }
+}
@@ -0,0 +1,184 @@
+use super::{ File, Star };
+use super::solution::{ self, Solution };
+use super::utils::{ escape_html, svg };
+use std::fmt::Write;
+use std::path::Path;
+use std::time::{ Duration, Instant };
+
+/// Run every day and write the results out as `index.html` (with everything
+/// embedded) and `README.md` (with renders alongside as SVG files) into `out_dir`.
+/// `progress` hears about each day just before it's run.
+pub fn report(out_dir: &Path, mut progress: impl FnMut(&Solution)) -> Result<String, anyhow::Error> {
+    let days: Vec<DayReport> = solution::all()
+        .into_iter()
+        .map(|solution| {
+            progress(&solution);
+            DayReport::run(solution)
+        })
+        .collect();
+
+    std::fs::create_dir_all(out_dir)?;
+    for day in &days {
+        if let Some(Ok(svg)) = &day.render {
+            std::fs::write(out_dir.join(day.svg_name()), svg)?;
+        }
+    }
+    std::fs::write(out_dir.join("index.html"), html(&days))?;
+    std::fs::write(out_dir.join("README.md"), markdown(&days))?;
+
+    Ok(format!("Wrote report to {}", out_dir.display()))
+}
+
+struct DayReport {
+    solution: Solution,
+    /// How long parsing alone takes, for days that can do that.
+    parse: Option<Result<Duration, String>>,
+    stars: Vec<StarReport>,
+    render: Option<Result<String, String>>,
+}
+
+struct StarReport {
+    star: Star,
+    answer: Result<String, String>,
+    elapsed: Duration,
+}
+
+impl DayReport {
+    fn run(solution: Solution) -> DayReport {
+        let file = solution.load_input(None).map_err(|e| format!("{e}"));
+
+        let stars = [Star::One, Star::Two].into_iter().map(|star| {
+            let (answer, elapsed) = match &file {
+                Ok(f) => {
+                    let (answer, elapsed) = solution.run_timed(star, File { contents: f.contents.clone() });
+                    (answer.map_err(|e| format!("{e}")), elapsed)
+                },
+                Err(e) => (Err(e.clone()), Duration::ZERO)
+            };
+            StarReport { star, answer, elapsed }
+        }).collect();
+
+        // Parse after solving, so that both are timed from a similarly warm start.
+        let parse = solution.parse.map(|parse| {
+            let contents = file.as_ref().map_err(|e| e.clone())?;
+            let start = Instant::now();
+            parse(&contents.contents).map_err(|e| format!("{e}"))?;
+            Ok(start.elapsed())
+        });
+
+        let render = solution.visualise.map(|visualise| {
+            let f = file.as_ref().map_err(|e| e.clone())?;
+            let text = visualise(File { contents: f.contents.clone() }).map_err(|e| format!("{e}"))?;
+            Ok(svg(&text))
+        });
+
+        DayReport { solution, parse, stars, render }
+    }
+    fn svg_name(&self) -> String {
+        format!("{}-day{:02}.svg", self.solution.year, self.solution.day)
+    }
+    /// Time spent solving a star once the (separately timed) parsing is taken off.
+    fn solve_time(&self, star: &StarReport) -> Duration {
+        match &self.parse {
+            Some(Ok(parse)) => star.elapsed.saturating_sub(*parse),
+            _ => star.elapsed
+        }
+    }
+    fn parse_time(&self) -> String {
+        match &self.parse {
+            None => "-".to_owned(),
+            Some(Ok(d)) => format!("{d:.2?}"),
+            Some(Err(_)) => "error".to_owned(),
+        }
+    }
+}
+
+fn html(days: &[DayReport]) -> String {
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Advent of Code</title>\n<style>\n\
+        body { font-family: sans-serif; max-width: 60em; margin: 2em auto; }\n\
+        td, th { padding: 0 1em; text-align: left; vertical-align: top; }\n\
+        pre { margin: 0; }\n\
+        .error { color: #b00; }\n\
+        svg { max-width: 100%; height: auto; }\n\
+        </style>\n</head>\n<body>\n<h1>Advent of Code</h1>\n");
+
+    out.push_str("<table>\n<tr><th>Day</th><th>Title</th><th>Star 1</th><th>Star 2</th><th>Parse</th><th>Solve 1</th><th>Solve 2</th></tr>\n");
+    for day in days {
+        let s = &day.solution;
+        let _ = write!(out, "<tr><td><a href=\"#{}-{}\">{} day {}</a></td><td>{}</td>", s.year, s.day, s.year, s.day, escape_html(s.title));
+        for star in &day.stars {
+            match &star.answer {
+                Ok(a) => { let _ = write!(out, "<td><pre>{}</pre></td>", escape_html(a)); },
+                Err(_) => out.push_str("<td class=\"error\">error</td>"),
+            }
+        }
+        let _ = write!(out, "<td>{}</td>", day.parse_time());
+        for star in &day.stars {
+            let _ = write!(out, "<td>{:.2?}</td>", day.solve_time(star));
+        }
+        out.push_str("</tr>\n");
+    }
+    out.push_str("</table>\n");
+
+    for day in days {
+        let s = &day.solution;
+        let _ = writeln!(out, "<h2 id=\"{}-{}\">{} day {}: {}</h2>\n<p>{}</p>", s.year, s.day, s.year, s.day, escape_html(s.title), escape_html(s.description));
+        for star in &day.stars {
+            let _ = write!(out, "<h3>Star {}</h3>\n<p>Took {:.2?} (parse {}, solve {:.2?})</p>\n", star.star.level(), star.elapsed, day.parse_time(), day.solve_time(star));
+            match &star.answer {
+                Ok(a) => { let _ = writeln!(out, "<pre>{}</pre>", escape_html(a)); },
+                Err(e) => { let _ = writeln!(out, "<pre class=\"error\">{}</pre>", escape_html(e)); },
+            }
+        }
+        match &day.render {
+            Some(Ok(svg)) => { let _ = writeln!(out, "<h3>Render</h3>\n{svg}"); },
+            Some(Err(e)) => { let _ = writeln!(out, "<h3>Render</h3>\n<pre class=\"error\">{}</pre>", escape_html(e)); },
+            None => {}
+        }
+    }
+
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn markdown(days: &[DayReport]) -> String {
+    let mut out = String::from("# Advent of Code\n\n");
+
+    out.push_str("| Day | Title | Star 1 | Star 2 | Parse | Solve 1 | Solve 2 |\n|---|---|---|---|---|---|---|\n");
+    for day in days {
+        let s = &day.solution;
+        let _ = write!(out, "| {} day {} | {} |", s.year, s.day, s.title);
+        for star in &day.stars {
+            match &star.answer {
+                // Multi-line answers (like the CRT screen) are shown in the day's section.
+                Ok(a) if !a.contains('\n') => { let _ = write!(out, " `{a}` |"); },
+                Ok(_) => out.push_str(" (see below) |"),
+                Err(_) => out.push_str(" error |"),
+            }
+        }
+        let _ = write!(out, " {} |", day.parse_time());
+        for star in &day.stars {
+            let _ = write!(out, " {:.2?} |", day.solve_time(star));
+        }
+        out.push('\n');
+    }
+
+    for day in days {
+        let s = &day.solution;
+        let _ = write!(out, "\n## {} day {}: {}\n\n{}\n", s.year, s.day, s.title, s.description);
+        for star in &day.stars {
+            let _ = write!(out, "\n### Star {}\n\nTook {:.2?} (parse {}, solve {:.2?})\n\n", star.star.level(), star.elapsed, day.parse_time(), day.solve_time(star));
+            match &star.answer {
+                Ok(a) => { let _ = writeln!(out, "```\n{a}\n```"); },
+                Err(e) => { let _ = writeln!(out, "```\nError: {e}\n```"); },
+            }
+        }
+        match &day.render {
+            Some(Ok(_)) => { let _ = writeln!(out, "\n![{} day {} render]({})", s.year, s.day, day.svg_name()); },
+            Some(Err(e)) => { let _ = writeln!(out, "\nCouldn't render: {e}"); },
+            None => {}
+        }
+    }
+    out
+}
@@ -0,0 +1,58 @@
+/// A small, seedable random number generator (SplitMix64), so that generated
+/// inputs come out the same for a given seed everywhere.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed)
+    }
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+    /// A number from `0` up to but not including `n`.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "Can't pick a number below 0");
+        (self.next_u64() % n as u64) as usize
+    }
+    /// A number from `lo` to `hi` inclusive.
+    pub fn range(&mut self, lo: i64, hi: i64) -> i64 {
+        assert!(lo <= hi, "Empty range {lo}..={hi}");
+        lo + (self.next_u64() % (hi - lo + 1) as u64) as i64
+    }
+    /// True with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn repeatable() {
+        // The first output of SplitMix64 seeded with 0.
+        assert_eq!(Rng::new(0).next_u64(), 0xe220a8397b1dcdaf);
+
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        for _ in 0..100 {
+            let n = a.range(-5, 5);
+            assert_eq!(n, b.range(-5, 5));
+            assert!((-5..=5).contains(&n));
+        }
+    }
+}
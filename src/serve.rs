@@ -0,0 +1,332 @@
+use super::{ File, Star };
+use super::history::{ self, History, Record };
+use super::solution::{ self, Solution };
+use super::utils::{ self, escape_html, svg };
+use std::collections::HashMap;
+use std::io::{ BufRead, BufReader, Write };
+use std::net::{ TcpListener, TcpStream };
+use std::sync::{ Arc, Mutex };
+use std::time::Duration;
+
+/// Serve a page per day on localhost, showing answers, timings and visualisations.
+/// Runs made through it are kept in the run history, alongside everything else.
+pub fn serve(port: u16) -> Result<(), anyhow::Error> {
+    let listener = TcpListener::bind(("127.0.0.1", port))?;
+    println!("Serving on http://{}", listener.local_addr()?);
+    serve_on(listener, Arc::new(Mutex::new(State::new(History::from_env()))))
+}
+
+fn serve_on(listener: TcpListener, state: Arc<Mutex<State>>) -> Result<(), anyhow::Error> {
+    for stream in listener.incoming() {
+        let stream = stream?;
+        let state = state.clone();
+        std::thread::spawn(move || {
+            if let Err(e) = handle(stream, &state) {
+                eprintln!("Error handling request: {e:?}");
+            }
+        });
+    }
+    Ok(())
+}
+
+struct State {
+    /// Where runs are recorded, and the timing history shown comes from.
+    history: History,
+    visualisations: HashMap<(u16, u8), Result<String, String>>,
+}
+
+impl State {
+    fn new(history: History) -> State {
+        State { history, visualisations: HashMap::new() }
+    }
+}
+
+struct Request {
+    method: String,
+    path: String,
+    headers: HashMap<String, String>,
+    body: Vec<u8>,
+}
+
+struct Response {
+    status: &'static str,
+    content_type: &'static str,
+    headers: Vec<(&'static str, String)>,
+    body: String,
+}
+
+impl Response {
+    fn html(body: String) -> Response {
+        Response { status: "200 OK", content_type: "text/html; charset=utf-8", headers: vec![], body }
+    }
+    fn text(status: &'static str, body: String) -> Response {
+        Response { status, content_type: "text/plain; charset=utf-8", headers: vec![], body }
+    }
+    fn redirect(to: String) -> Response {
+        Response { status: "303 See Other", content_type: "text/plain", headers: vec![("Location", to)], body: String::new() }
+    }
+}
+
+fn handle(stream: TcpStream, state: &Mutex<State>) -> Result<(), anyhow::Error> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let req = read_request(&mut reader)?;
+    let res = respond(&req, state);
+
+    let mut stream = stream;
+    write!(stream, "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n", res.status, res.content_type, res.body.len())?;
+    for (name, value) in &res.headers {
+        write!(stream, "{name}: {value}\r\n")?;
+    }
+    write!(stream, "\r\n{}", res.body)?;
+    Ok(())
+}
+
+fn read_request(reader: &mut impl BufRead) -> Result<Request, anyhow::Error> {
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_owned();
+    let path = parts.next().unwrap_or("/").to_owned();
+
+    let mut headers = HashMap::new();
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.insert(name.trim().to_lowercase(), value.trim().to_owned());
+        }
+    }
+
+    const MAX_BODY: usize = 64 * 1024 * 1024;
+    let len: usize = headers.get("content-length").and_then(|l| l.parse().ok()).unwrap_or(0);
+    if len > MAX_BODY {
+        anyhow::bail!("Request body of {len} bytes is too large");
+    }
+    let mut body = vec![0; len];
+    reader.read_exact(&mut body)?;
+
+    Ok(Request { method, path, headers, body })
+}
+
+fn respond(req: &Request, state: &Mutex<State>) -> Response {
+    let path = req.path.split('?').next().unwrap_or_default();
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+    let find = |year: &str, day: &str| -> Option<Solution> {
+        solution::find(year.parse().ok()?, day.parse().ok()?).ok()
+    };
+
+    match (req.method.as_str(), segments.as_slice()) {
+        ("GET", []) => Response::html(index_page()),
+        ("GET", [year, day]) => match find(year, day) {
+            Some(solution) => Response::html(day_page(&solution, state)),
+            None => Response::text("404 Not Found", "No such day".to_owned()),
+        },
+        ("POST", [year, day, "run"]) => match find(year, day) {
+            Some(solution) => {
+                let summary = run(&solution, req, state);
+                let wants_html = req.headers.get("accept").is_some_and(|a| a.contains("text/html"));
+                if wants_html {
+                    Response::redirect(format!("/{}/{}", solution.year, solution.day))
+                } else {
+                    Response::text("200 OK", summary)
+                }
+            },
+            None => Response::text("404 Not Found", "No such day".to_owned()),
+        },
+        _ => Response::text("404 Not Found", "Not found".to_owned()),
+    }
+}
+
+/// Run both stars, against an uploaded input if one was sent, otherwise the
+/// usual one, and record how they went.
+fn run(solution: &Solution, req: &Request, state: &Mutex<State>) -> String {
+    let file = match uploaded_input(req) {
+        Some((_, contents)) => File { contents },
+        None => match solution.load_input(None) {
+            Ok(file) => file,
+            Err(e) => return format!("Error: {e}"),
+        },
+    };
+
+    let rev = history::git_rev();
+    let records: Vec<Record> = [Star::One, Star::Two]
+        .into_iter()
+        .map(|star| history::measure(solution, star, &file, &rev, 1))
+        .collect();
+    if let Err(e) = state.lock().unwrap().history.append(&records) {
+        eprintln!("Warning: couldn't save run history: {e}");
+    }
+
+    records
+        .iter()
+        .map(|r| format!("Star {}: {} ({:?})\n", r.star, r.answer.as_deref().unwrap_or_else(|e| e), Duration::from_nanos(r.best_ns)))
+        .collect()
+}
+
+/// Find the input in either a form upload or the raw request body.
+fn uploaded_input(req: &Request) -> Option<(String, String)> {
+    let body = String::from_utf8_lossy(&req.body).into_owned();
+    let content_type = req.headers.get("content-type").map(|s| s.as_str()).unwrap_or_default();
+
+    if let Some(boundary) = content_type.split("boundary=").nth(1) {
+        let boundary = format!("--{}", boundary.trim_matches('"'));
+        let part = body.split(&boundary).find(|p| p.contains("name=\"input\""))?;
+        let (headers, contents) = part.split_once("\r\n\r\n")?;
+        let name = regex!("filename=\"([^\"]*)\"")
+            .captures(headers)
+            .and_then(|c| c.get(1))
+            .map(|m| m.as_str().to_owned())
+            .filter(|n| !n.is_empty())?;
+        let contents = contents.strip_suffix("\r\n").unwrap_or(contents);
+        Some((name, contents.to_owned()))
+    } else if !body.is_empty() {
+        Some(("uploaded".to_owned(), body))
+    } else {
+        None
+    }
+}
+
+fn index_page() -> String {
+    let mut items = String::new();
+    for s in solution::all() {
+        items.push_str(&format!(
+            "<li><a href=\"/{}/{}\">{} day {}: {}</a></li>\n",
+            s.year, s.day, s.year, s.day, escape_html(s.title)
+        ));
+    }
+    page("Advent of Code", &format!("<h1>Advent of Code</h1>\n<ul>\n{items}</ul>"))
+}
+
+fn day_page(solution: &Solution, state: &Mutex<State>) -> String {
+    let key = (solution.year, solution.day);
+
+    // Visualisations don't change, so render them once and keep them around.
+    let visualisation = solution.visualise.map(|visualise| {
+        if let Some(v) = state.lock().unwrap().visualisations.get(&key) {
+            return v.clone()
+        }
+        let v = solution.load_input(None).and_then(visualise).map(|v| svg(&v)).map_err(|e| format!("{e:?}"));
+        state.lock().unwrap().visualisations.insert(key, v.clone());
+        v
+    });
+
+    let runs: Vec<Record> = match state.lock().unwrap().history.load() {
+        Ok(records) => records.into_iter().filter(|r| (r.year, r.day) == key).collect(),
+        Err(e) => {
+            eprintln!("Warning: couldn't read run history: {e}");
+            vec![]
+        }
+    };
+    // So that runs against the usual input can be told apart from uploaded ones.
+    let usual_input = solution.load_input(None).ok().map(|f| format!("{:016x}", utils::hash(f.contents.as_bytes())));
+
+    let mut body = format!(
+        "<p><a href=\"/\">All days</a></p>\n<h1>{} day {}: {}</h1>\n<p>{}</p>\n",
+        solution.year, solution.day, escape_html(solution.title), escape_html(solution.description)
+    );
+
+    body.push_str("<h2>Answers</h2>\n");
+    for star in [Star::One, Star::Two] {
+        let answer = match runs.iter().rev().find(|r| r.star == star.level()).map(|r| &r.answer) {
+            None => "<em>Not run yet</em>".to_owned(),
+            Some(Ok(a)) => format!("<pre>{}</pre>", escape_html(a)),
+            Some(Err(e)) => format!("<pre class=\"error\">{}</pre>", escape_html(e)),
+        };
+        body.push_str(&format!("<h3>Star {}</h3>\n{answer}\n", star.level()));
+    }
+
+    body.push_str(&format!(
+        "<form method=\"post\" action=\"/{}/{}/run\" enctype=\"multipart/form-data\">\n\
+         <input type=\"file\" name=\"input\"> <button type=\"submit\">Run</button> \
+         (leave the file empty to use the usual input)\n</form>\n",
+        solution.year, solution.day
+    ));
+
+    if !runs.is_empty() {
+        body.push_str("<h2>Timing history</h2>\n<table>\n<tr><th>When</th><th>Star</th><th>Revision</th><th>Input</th><th>Best</th><th>Runs</th><th>Result</th></tr>\n");
+        for run in runs.iter().rev() {
+            let input = if usual_input.as_ref() == Some(&run.input_hash) { "usual".to_owned() } else { format!("other ({})", run.input_hash) };
+            body.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{:?}</td><td>{}</td><td>{}</td></tr>\n",
+                run.at, run.star, escape_html(&run.rev), escape_html(&input), Duration::from_nanos(run.best_ns), run.runs,
+                if run.answer.is_ok() { "ok" } else { "error" }
+            ));
+        }
+        body.push_str("</table>\n");
+    }
+
+    if let Some(v) = visualisation {
+        body.push_str("<h2>Visualisation</h2>\n");
+        match v {
+            Ok(v) => body.push_str(&v),
+            Err(e) => body.push_str(&format!("<pre class=\"error\">{}</pre>\n", escape_html(&e))),
+        }
+    }
+
+    page(solution.title, &body)
+}
+
+fn page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n\
+         body {{ font-family: sans-serif; max-width: 60em; margin: 2em auto; }}\n\
+         svg {{ max-width: 100%; height: auto; }}\n\
+         pre.error {{ color: #b00; }}\n\
+         td, th {{ padding: 0 1em; text-align: left; }}\n\
+         </style>\n</head>\n<body>\n{body}\n</body>\n</html>\n",
+        escape_html(title)
+    )
+}
+
+// Uses day 1 to have something to run.
+#[cfg(all(test, feature = "day01"))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn runs_uploaded_input() {
+        let dir = utils::TempDir::new("serve");
+        let history = || History { path: dir.path.join("history.jsonl") };
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let state = Arc::new(Mutex::new(State::new(history())));
+        std::thread::spawn(move || serve_on(listener, state));
+
+        let index = ureq::get(&format!("http://{addr}/")).call().unwrap().into_string().unwrap();
+        assert!(index.contains("href=\"/2022/1\""));
+
+        let summary = ureq::post(&format!("http://{addr}/2022/1/run"))
+            .send_string("1\n2\n\n3\n\n4\n\n")
+            .unwrap()
+            .into_string()
+            .unwrap();
+        assert!(summary.starts_with("Star 1: 4 ("), "{summary}");
+
+        let page = ureq::get(&format!("http://{addr}/2022/1")).call().unwrap().into_string().unwrap();
+        assert!(page.contains("Calorie Counting"));
+        assert!(page.contains("<pre>4</pre>"));
+        assert!(page.contains("<td>other ("));
+
+        // The runs are in the history, so are still there for the next server.
+        assert_eq!(history().load().unwrap().len(), 2);
+        let solution = solution::find(2022, 1).unwrap();
+        let page = day_page(&solution, &Mutex::new(State::new(history())));
+        assert!(page.contains("<pre>4</pre>"));
+
+        let missing = ureq::get(&format!("http://{addr}/2022/26")).call();
+        assert!(matches!(missing, Err(ureq::Error::Status(404, _))));
+    }
+
+    #[test]
+    #[cfg(all(feature = "viz", feature = "day14"))]
+    fn renders_visualisations() {
+        let solution = solution::find(2022, 14).unwrap();
+        let dir = utils::TempDir::new("serve-viz");
+        let page = day_page(&solution, &Mutex::new(State::new(History { path: dir.path.join("history.jsonl") })));
+        assert!(page.contains("<svg "), "{page}");
+        assert!(!page.contains("class=\"error\""));
+    }
+}
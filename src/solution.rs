@@ -0,0 +1,169 @@
+use super::{ File, Star };
+use super::input;
+use super::rng::Rng;
+use std::io::BufRead;
+use std::time::{ Duration, Instant };
+
+/// Solve a star (or draw something) given an input.
+pub type RunFn = fn(File) -> Result<String, anyhow::Error>;
+/// Parse an input without solving anything.
+pub type ParseFn = fn(&str) -> Result<(), anyhow::Error>;
+/// Solve a star reading the input as it goes, rather than all in one go.
+pub type StreamFn = fn(&mut dyn BufRead) -> Result<String, anyhow::Error>;
+/// Make up a valid input of roughly some size.
+pub type GenFn = fn(&mut Rng, usize) -> String;
+
+/// A single day's puzzle, and the functions to solve each of its stars.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub year: u16,
+    pub day: u8,
+    pub title: &'static str,
+    pub description: &'static str,
+    /// Does this day read an input at all?
+    pub input: bool,
+    pub star1: RunFn,
+    pub star2: RunFn,
+    /// Slow but obviously correct ways to solve each star, to check the real
+    /// ones against (see [`crate::differential`]).
+    pub reference1: Option<RunFn>,
+    pub reference2: Option<RunFn>,
+    /// Solve each star straight from a reader, for days that don't need the
+    /// whole input in memory at once.
+    pub stream1: Option<StreamFn>,
+    pub stream2: Option<StreamFn>,
+    /// Just parse the input, for days where that's a separate step, so that
+    /// we can tell how long parsing takes compared to solving.
+    pub parse: Option<ParseFn>,
+    /// Draw something interesting about the puzzle as text, for days that
+    /// simulate things or work on a grid.
+    pub visualise: Option<RunFn>,
+    /// Generate random inputs, for testing against more than the one we have.
+    pub generate: Option<GenFn>,
+    /// Other things to do with the input besides solving the stars.
+    pub extras: &'static [Extra],
+    /// The day module's source, so that we can tell when it's changed.
+    pub source: &'static str,
+}
+
+/// Something more to do with a day's input than solving its stars, like
+/// reporting on it. Any options it has are read from the day's params.
+#[derive(Clone, Copy)]
+pub struct Extra {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub run: RunFn,
+}
+
+/// Build a [`Solution`] from a day module exposing `star1` and `star2`
+/// functions whose answers implement `Display`.
+#[cfg(days)]
+macro_rules! solution {
+    ($year:literal, $day:literal, $module:ident, $title:literal, $description:literal) => {
+        $crate::solution::Solution {
+            year: $year,
+            day: $day,
+            title: $title,
+            description: $description,
+            input: true,
+            star1: |file| $module::star1(file).map(|a| a.to_string()),
+            star2: |file| $module::star2(file).map(|a| a.to_string()),
+            reference1: None,
+            reference2: None,
+            stream1: None,
+            stream2: None,
+            parse: None,
+            visualise: None,
+            generate: None,
+            extras: &[],
+            source: include_str!(concat!(stringify!($module), ".rs")),
+        }
+    }
+}
+
+impl Solution {
+    /// Use the file given, or else the input built into the binary, or else
+    /// find the input for this day.
+    pub fn load_input(&self, file: Option<File>) -> Result<File, anyhow::Error> {
+        match file {
+            Some(file) => Ok(file),
+            None if !self.input => Ok(File { contents: String::new() }),
+            None => match input::embedded(self.year, self.day) {
+                Some(contents) => Ok(File { contents: contents.to_owned() }),
+                None => input::Provider::from_env().get(self.year, self.day)
+            }
+        }
+    }
+    pub fn run(&self, star: Star, file: File) -> Result<String, anyhow::Error> {
+        match star {
+            Star::One => (self.star1)(file),
+            Star::Two => (self.star2)(file),
+        }
+    }
+    pub fn extra(&self, name: &str) -> Result<&Extra, anyhow::Error> {
+        self.extras.iter().find(|e| e.name == name).ok_or_else(|| {
+            let names: Vec<&str> = self.extras.iter().map(|e| e.name).collect();
+            match names.is_empty() {
+                true => anyhow::anyhow!("{} day {} doesn't have any extras", self.year, self.day),
+                false => anyhow::anyhow!("{} day {} has no extra called '{name}'; try one of {}", self.year, self.day, names.join(", ")),
+            }
+        })
+    }
+    /// The reference implementation of a star, if it has one.
+    pub fn reference(&self, star: Star) -> Option<RunFn> {
+        match star {
+            Star::One => self.reference1,
+            Star::Two => self.reference2,
+        }
+    }
+    /// Solve a star from a reader; streamed, if the day can do that, and
+    /// otherwise read in full first.
+    pub fn run_streamed(&self, star: Star, reader: &mut dyn BufRead) -> Result<String, anyhow::Error> {
+        let stream = match star {
+            Star::One => self.stream1,
+            Star::Two => self.stream2,
+        };
+        match stream {
+            Some(stream) => stream(reader),
+            None => {
+                let mut contents = String::new();
+                reader.read_to_string(&mut contents)?;
+                self.run(star, File { contents })
+            }
+        }
+    }
+    /// A random input for this day; the same `seed` and `size` always give the
+    /// same input. What `size` means is up to the day, but bigger is bigger.
+    pub fn generate(&self, seed: u64, size: usize) -> Result<String, anyhow::Error> {
+        match self.generate {
+            Some(generate) => Ok(generate(&mut Rng::new(seed), size)),
+            None => anyhow::bail!("{} day {} can't generate inputs", self.year, self.day),
+        }
+    }
+    /// Run a star, also returning how long it took to solve.
+    pub fn run_timed(&self, star: Star, file: File) -> (Result<String, anyhow::Error>, Duration) {
+        let start = Instant::now();
+        let res = self.run(star, file);
+        (res, start.elapsed())
+    }
+}
+
+/// Every solution for every year, in order.
+pub fn all() -> Vec<Solution> {
+    let mut solutions = Vec::new();
+    solutions.extend(super::y2022::solutions());
+    solutions.sort_by_key(|s| (s.year, s.day));
+    solutions
+}
+
+/// The most recent year that we have solutions for.
+pub fn latest_year() -> u16 {
+    all().iter().map(|s| s.year).max().unwrap_or(2022)
+}
+
+pub fn find(year: u16, day: u8) -> Result<Solution, anyhow::Error> {
+    all()
+        .into_iter()
+        .find(|s| s.year == year && s.day == day)
+        .ok_or_else(|| anyhow::anyhow!("No solution for {year} day {day}"))
+}
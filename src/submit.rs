@@ -0,0 +1,314 @@
+use super::input::HttpSource;
+use super::utils;
+use std::io::Write;
+use std::path::{ Path, PathBuf };
+
+/// Submit an answer for the given day and level (star), after checking our
+/// record of earlier attempts to make sure it's worth sending.
+pub fn submit(year: u16, day: u8, level: u8, answer: &str) -> Result<String, anyhow::Error> {
+    let answer = answer.trim();
+    let log = Log { path: utils::data_dir().join("submissions.tsv") };
+
+    log.check(year, day, level, answer, utils::now())?;
+    let outcome = post(&HttpSource::from_env(), year, day, level, answer)?;
+    log.record(&Attempt { at: utils::now(), year, day, level, answer: answer.to_owned(), outcome })?;
+
+    Ok(outcome.to_string())
+}
+
+/// What the site made of an answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Right,
+    Wrong { cooldown: u64 },
+    TooHigh { cooldown: u64 },
+    TooLow { cooldown: u64 },
+    /// We submitted too soon after the last attempt.
+    Wait { seconds: u64 },
+    /// The level is already solved (or not unlocked yet).
+    WrongLevel,
+}
+
+impl Outcome {
+    fn is_wrong(&self) -> bool {
+        matches!(self, Outcome::Wrong { .. } | Outcome::TooHigh { .. } | Outcome::TooLow { .. })
+    }
+    /// How long to hold off before the next attempt.
+    fn cooldown(&self) -> u64 {
+        match self {
+            Outcome::Wrong { cooldown } | Outcome::TooHigh { cooldown } | Outcome::TooLow { cooldown } => *cooldown,
+            Outcome::Wait { seconds } => *seconds,
+            Outcome::Right | Outcome::WrongLevel => 0,
+        }
+    }
+    fn parse(body: &str) -> Result<Outcome, anyhow::Error> {
+        let text = strip_tags(body);
+
+        let outcome = if text.contains("That's the right answer") {
+            Outcome::Right
+        } else if text.contains("That's not the right answer") {
+            let cooldown = parse_cooldown(&text).unwrap_or(60);
+            if text.contains("too high") {
+                Outcome::TooHigh { cooldown }
+            } else if text.contains("too low") {
+                Outcome::TooLow { cooldown }
+            } else {
+                Outcome::Wrong { cooldown }
+            }
+        } else if text.contains("You gave an answer too recently") {
+            Outcome::Wait { seconds: parse_time_left(&text).unwrap_or(60) }
+        } else if text.contains("You don't seem to be solving the right level") {
+            Outcome::WrongLevel
+        } else {
+            anyhow::bail!("Didn't understand the response: {}", text.trim())
+        };
+        Ok(outcome)
+    }
+    fn name(&self) -> &'static str {
+        match self {
+            Outcome::Right => "right",
+            Outcome::Wrong { .. } => "wrong",
+            Outcome::TooHigh { .. } => "too-high",
+            Outcome::TooLow { .. } => "too-low",
+            Outcome::Wait { .. } => "wait",
+            Outcome::WrongLevel => "wrong-level",
+        }
+    }
+    fn from_name(name: &str, cooldown: u64) -> Option<Outcome> {
+        Some(match name {
+            "right" => Outcome::Right,
+            "wrong" => Outcome::Wrong { cooldown },
+            "too-high" => Outcome::TooHigh { cooldown },
+            "too-low" => Outcome::TooLow { cooldown },
+            "wait" => Outcome::Wait { seconds: cooldown },
+            "wrong-level" => Outcome::WrongLevel,
+            _ => return None
+        })
+    }
+}
+
+impl std::fmt::Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Right => write!(f, "That's the right answer!"),
+            Outcome::Wrong { cooldown } => write!(f, "That's not the right answer (wait {cooldown}s before trying again)"),
+            Outcome::TooHigh { cooldown } => write!(f, "That answer is too high (wait {cooldown}s before trying again)"),
+            Outcome::TooLow { cooldown } => write!(f, "That answer is too low (wait {cooldown}s before trying again)"),
+            Outcome::Wait { seconds } => write!(f, "Answered too recently; {seconds}s left to wait"),
+            Outcome::WrongLevel => write!(f, "That level is already solved or not unlocked yet"),
+        }
+    }
+}
+
+fn post(http: &HttpSource, year: u16, day: u8, level: u8, answer: &str) -> Result<Outcome, anyhow::Error> {
+    let Some(session) = &http.session else {
+        anyhow::bail!("Set AOC_SESSION to your session token to submit answers")
+    };
+
+    let url = format!("{}/{year}/day/{day}/answer", http.base_url.trim_end_matches('/'));
+    let res = ureq::post(&url)
+        .set("Cookie", &format!("session={session}"))
+        .send_form(&[("level", &level.to_string()), ("answer", answer)]);
+
+    match res {
+        Ok(res) => Outcome::parse(&res.into_string()?),
+        Err(ureq::Error::Status(code, _)) => anyhow::bail!("Submitting to {url} failed with status {code}"),
+        Err(e) => Err(e.into())
+    }
+}
+
+/// Turn "Please wait one minute" or "please wait 5 minutes" into seconds.
+fn parse_cooldown(text: &str) -> Option<u64> {
+    let caps = regex!(r"(?i)wait (one|[0-9]+) minutes?").captures(text)?;
+    match caps.get(1)?.as_str() {
+        "one" => Some(60),
+        n => n.parse::<u64>().ok().map(|n| n * 60)
+    }
+}
+
+/// Turn "You have 1m 23s left to wait" into seconds.
+fn parse_time_left(text: &str) -> Option<u64> {
+    let caps = regex!(r"You have (?:([0-9]+)m )?([0-9]+)s left to wait").captures(text)?;
+    let mins: u64 = caps.get(1).map_or(Some(0), |m| m.as_str().parse().ok())?;
+    let secs: u64 = caps.get(2)?.as_str().parse().ok()?;
+    Some(mins * 60 + secs)
+}
+
+fn strip_tags(html: &str) -> String {
+    regex!("<[^>]*>").replace_all(html, "").into_owned()
+}
+
+/// One line in the submissions log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Attempt {
+    at: u64,
+    year: u16,
+    day: u8,
+    level: u8,
+    answer: String,
+    outcome: Outcome,
+}
+
+impl Attempt {
+    fn to_line(&self) -> String {
+        let Attempt { at, year, day, level, answer, outcome } = self;
+        format!("{at}\t{year}\t{day}\t{level}\t{}\t{}\t{}", escape(answer), outcome.name(), outcome.cooldown())
+    }
+    fn from_line(line: &str) -> Option<Attempt> {
+        let mut parts = line.split('\t');
+        let mut next = || parts.next();
+        Some(Attempt {
+            at: next()?.parse().ok()?,
+            year: next()?.parse().ok()?,
+            day: next()?.parse().ok()?,
+            level: next()?.parse().ok()?,
+            answer: unescape(next()?)?,
+            outcome: {
+                let name = next()?;
+                let cooldown = next()?.parse().ok()?;
+                Outcome::from_name(name, cooldown)?
+            },
+        })
+    }
+}
+
+/// Backslash escape an answer so that it fits in one field of a line, even if
+/// it has tabs or newlines in it (like a CRT screen's worth of pixels).
+fn escape(answer: &str) -> String {
+    answer.replace('\\', "\\\\").replace('\t', "\\t").replace('\n', "\\n").replace('\r', "\\r")
+}
+
+fn unescape(field: &str) -> Option<String> {
+    let mut out = String::new();
+    let mut chars = field.chars();
+    while let Some(c) = chars.next() {
+        out.push(match c {
+            '\\' => match chars.next()? {
+                't' => '\t',
+                'n' => '\n',
+                'r' => '\r',
+                '\\' => '\\',
+                _ => return None,
+            },
+            c => c,
+        });
+    }
+    Some(out)
+}
+
+/// Every answer we've submitted, one tab separated line each.
+struct Log {
+    path: PathBuf,
+}
+
+impl Log {
+    fn attempts(&self) -> Result<Vec<Attempt>, anyhow::Error> {
+        match std::fs::read_to_string(&self.path) {
+            Ok(s) => Ok(s.lines().filter_map(Attempt::from_line).collect()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(e.into())
+        }
+    }
+    fn record(&self, attempt: &Attempt) -> Result<(), anyhow::Error> {
+        std::fs::create_dir_all(self.path.parent().unwrap_or(Path::new(".")))?;
+        let mut file = std::fs::OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}", attempt.to_line())?;
+        Ok(())
+    }
+    /// Refuse answers we already know are wrong, and anything sent before the
+    /// cooldown from the last attempt on this day is up.
+    fn check(&self, year: u16, day: u8, level: u8, answer: &str, now: u64) -> Result<(), anyhow::Error> {
+        let attempts = self.attempts()?;
+        let attempts = attempts.iter().filter(|a| a.year == year && a.day == day);
+
+        for a in attempts.clone().filter(|a| a.level == level) {
+            if a.outcome == Outcome::Right {
+                anyhow::bail!("Day {day} star {level} was already solved with '{}'", a.answer);
+            }
+            if a.outcome.is_wrong() && a.answer == answer {
+                anyhow::bail!("'{answer}' was already submitted and was {}", a.outcome.name());
+            }
+            // Numeric answers can be ruled out by earlier too high/low ones too.
+            if let (Ok(n), Ok(prev)) = (answer.parse::<i128>(), a.answer.parse::<i128>()) {
+                match a.outcome {
+                    Outcome::TooHigh { .. } if n >= prev => anyhow::bail!("'{answer}' is too high; '{prev}' already was"),
+                    Outcome::TooLow { .. } if n <= prev => anyhow::bail!("'{answer}' is too low; '{prev}' already was"),
+                    _ => {}
+                }
+            }
+        }
+
+        if let Some(last) = attempts.max_by_key(|a| a.at) {
+            let ready_at = last.at + last.outcome.cooldown();
+            if now < ready_at {
+                anyhow::bail!("Still cooling down from the last attempt; wait {}s", ready_at - now);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses_responses() {
+        let cases = [
+            ("<article><p>That's the right answer!  You are one gold star closer.</p></article>", Outcome::Right),
+            ("<p>That's not the right answer; your answer is too high.  Please wait one minute before trying again.</p>", Outcome::TooHigh { cooldown: 60 }),
+            ("<p>That's not the right answer; your answer is too low.  Please wait 5 minutes before trying again.</p>", Outcome::TooLow { cooldown: 300 }),
+            ("<p>That's not the right answer.  If you're stuck, <a href=\"/r\">ask</a>.  Please wait one minute before trying again.</p>", Outcome::Wrong { cooldown: 60 }),
+            ("<p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait.</p>", Outcome::Wait { seconds: 65 }),
+            ("<p>You gave an answer too recently.  You have 42s left to wait.</p>", Outcome::Wait { seconds: 42 }),
+            ("<p>You don't seem to be solving the right level.  Did you already complete it?</p>", Outcome::WrongLevel),
+        ];
+        for (body, expected) in cases {
+            assert_eq!(Outcome::parse(body).unwrap(), expected, "{body}");
+        }
+        assert!(Outcome::parse("<html>Something else</html>").is_err());
+    }
+
+    #[test]
+    fn submits_and_keeps_track_of_attempts() {
+        let dir = utils::TempDir::new("submit");
+        let log = Log { path: dir.path.join("submissions.tsv") };
+
+        let (addr, server) = utils::serve_once("<p>That's not the right answer; your answer is too low.  Please wait one minute before trying again.</p>".to_owned());
+        let http = HttpSource { base_url: format!("http://{addr}"), session: Some("abc".to_owned()) };
+
+        log.check(2022, 1, 1, "100", 1000).unwrap();
+        let outcome = post(&http, 2022, 1, 1, "100").unwrap();
+        assert_eq!(outcome, Outcome::TooLow { cooldown: 60 });
+        log.record(&Attempt { at: 1000, year: 2022, day: 1, level: 1, answer: "100".to_owned(), outcome }).unwrap();
+
+        let req = server.join().unwrap();
+        assert!(req.starts_with("POST /2022/day/1/answer "));
+        assert!(req.ends_with("level=1&answer=100"));
+
+        // In the cooldown window:
+        assert!(log.check(2022, 1, 1, "200", 1030).is_err());
+        // Known to be wrong, or lower than something that's too low:
+        assert!(log.check(2022, 1, 1, "100", 2000).is_err());
+        assert!(log.check(2022, 1, 1, "50", 2000).is_err());
+        // Fine:
+        log.check(2022, 1, 1, "200", 2000).unwrap();
+        log.check(2022, 1, 2, "100", 2000).unwrap();
+        log.check(2022, 2, 1, "100", 1030).unwrap();
+    }
+
+    #[test]
+    fn keeps_track_of_multi_line_answers() {
+        let dir = utils::TempDir::new("submit-lines");
+        let log = Log { path: dir.path.join("submissions.tsv") };
+        let answer = "#..#\t\\\n.##.";
+        let attempt = Attempt { at: 1000, year: 2022, day: 10, level: 2, answer: answer.to_owned(), outcome: Outcome::Wrong { cooldown: 60 } };
+        assert_eq!(attempt.to_line().lines().count(), 1);
+        log.record(&attempt).unwrap();
+
+        assert_eq!(log.attempts().unwrap(), vec![attempt]);
+        assert!(log.check(2022, 10, 2, answer, 2000).is_err());
+        assert!(log.check(2022, 10, 2, "#..#", 1030).is_err());
+        log.check(2022, 10, 2, "#..#", 2000).unwrap();
+    }
+}
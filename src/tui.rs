@@ -0,0 +1,227 @@
+use super::Star;
+use super::solution::{ self, Solution };
+use ratatui::crossterm::event::{ self, Event, KeyCode, KeyEventKind };
+use ratatui::layout::{ Constraint, Layout };
+use ratatui::style::{ Color, Modifier, Style };
+use ratatui::text::{ Line, Span };
+use ratatui::widgets::{ Block, Borders, List, ListItem, ListState, Paragraph, Wrap };
+use ratatui::{ DefaultTerminal, Frame };
+use std::collections::HashMap;
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// Browse every day of a year, run stars and look at visualisations in the terminal.
+pub fn run(year: u16) -> Result<String, anyhow::Error> {
+    let mut terminal = ratatui::init();
+    let res = App::new(year).run(&mut terminal);
+    ratatui::restore();
+    res.map(|_| String::new())
+}
+
+/// The state of one star (or the visualisation) of one day.
+enum Status {
+    Running,
+    Done { answer: Result<String, String>, elapsed: Duration },
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum Task {
+    Star(Star),
+    Visualise,
+}
+
+struct App {
+    year: u16,
+    solutions: Vec<Solution>,
+    list: ListState,
+    statuses: HashMap<(usize, Task), Status>,
+    /// Showing a visualisation (for the given solution) rather than the details?
+    visualising: Option<usize>,
+    scroll: (u16, u16),
+    tx: mpsc::Sender<(usize, Task, Status)>,
+    rx: mpsc::Receiver<(usize, Task, Status)>,
+}
+
+impl App {
+    fn new(year: u16) -> Self {
+        let (tx, rx) = mpsc::channel();
+        App {
+            year,
+            solutions: solution::all().into_iter().filter(|s| s.year == year).collect(),
+            list: ListState::default().with_selected(Some(0)),
+            statuses: HashMap::new(),
+            visualising: None,
+            scroll: (0, 0),
+            tx,
+            rx,
+        }
+    }
+
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> Result<(), anyhow::Error> {
+        loop {
+            while let Ok((idx, task, status)) = self.rx.try_recv() {
+                self.statuses.insert((idx, task), status);
+            }
+
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if !event::poll(Duration::from_millis(100))? {
+                continue
+            }
+            let Event::Key(key) = event::read()? else {
+                continue
+            };
+            if key.kind != KeyEventKind::Press {
+                continue
+            }
+
+            let selected = self.list.selected().unwrap_or(0);
+            match (self.visualising, key.code) {
+                (_, KeyCode::Char('q')) => return Ok(()),
+                // With no days, there's nothing to do but leave.
+                (None, KeyCode::Esc) if self.solutions.is_empty() => return Ok(()),
+                _ if self.solutions.is_empty() => {},
+                (Some(_), KeyCode::Esc | KeyCode::Char('v')) => self.visualising = None,
+                (Some(_), KeyCode::Up | KeyCode::Char('k')) => self.scroll.0 = self.scroll.0.saturating_sub(1),
+                (Some(_), KeyCode::Down | KeyCode::Char('j')) => self.scroll.0 += 1,
+                (Some(_), KeyCode::Left | KeyCode::Char('h')) => self.scroll.1 = self.scroll.1.saturating_sub(1),
+                (Some(_), KeyCode::Right | KeyCode::Char('l')) => self.scroll.1 += 1,
+                (Some(idx), KeyCode::Char('r')) => self.start(idx, Task::Visualise),
+                (None, KeyCode::Esc) => return Ok(()),
+                (None, KeyCode::Up | KeyCode::Char('k')) => self.list.select_previous(),
+                (None, KeyCode::Down | KeyCode::Char('j')) if selected + 1 < self.solutions.len() => {
+                    self.list.select_next()
+                },
+                (None, KeyCode::Char('1')) => self.start(selected, Task::Star(Star::One)),
+                (None, KeyCode::Char('2')) => self.start(selected, Task::Star(Star::Two)),
+                (None, KeyCode::Char('r')) => {
+                    self.start(selected, Task::Star(Star::One));
+                    self.start(selected, Task::Star(Star::Two));
+                },
+                (None, KeyCode::Char('v')) if self.solutions[selected].visualise.is_some() => {
+                    self.visualising = Some(selected);
+                    self.scroll = (0, 0);
+                    if !self.statuses.contains_key(&(selected, Task::Visualise)) {
+                        self.start(selected, Task::Visualise);
+                    }
+                },
+                _ => {}
+            }
+        }
+    }
+
+    /// Run something in the background, so that slow days don't block the UI.
+    fn start(&mut self, idx: usize, task: Task) {
+        if let Some(Status::Running) = self.statuses.get(&(idx, task)) {
+            return
+        }
+        self.statuses.insert((idx, task), Status::Running);
+
+        let solution = self.solutions[idx];
+        let tx = self.tx.clone();
+        std::thread::spawn(move || {
+            let (answer, elapsed) = match solution.load_input(None) {
+                Err(e) => (Err(e), Duration::ZERO),
+                Ok(file) => match task {
+                    Task::Star(star) => solution.run_timed(star, file),
+                    Task::Visualise => {
+                        let start = std::time::Instant::now();
+                        let res = solution.visualise.map_or_else(
+                            || Err(anyhow::anyhow!("Nothing to visualise")),
+                            |visualise| visualise(file)
+                        );
+                        (res, start.elapsed())
+                    }
+                }
+            };
+            let answer = answer.map_err(|e| format!("{e:?}"));
+            let _ = tx.send((idx, task, Status::Done { answer, elapsed }));
+        });
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, help] = Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+
+        if let Some(idx) = self.visualising {
+            let solution = &self.solutions[idx];
+            let text = match self.statuses.get(&(idx, Task::Visualise)) {
+                None | Some(Status::Running) => "Drawing...".to_owned(),
+                Some(Status::Done { answer: Ok(s), .. }) => s.clone(),
+                Some(Status::Done { answer: Err(e), .. }) => format!("Error: {e}"),
+            };
+            let title = format!(" {} day {}: {} ", solution.year, solution.day, solution.title);
+            let viz = Paragraph::new(text)
+                .block(Block::default().borders(Borders::ALL).title(title))
+                .scroll(self.scroll);
+            frame.render_widget(viz, main);
+            frame.render_widget(Line::from(" arrows/hjkl: scroll   r: redraw   v/esc: back   q: quit"), help);
+            return
+        }
+
+        if self.solutions.is_empty() {
+            let empty = Paragraph::new(format!(
+                "No days for {} are built in. Build with the features for the days you want (see Cargo.toml), or pick another year with --year.",
+                self.year
+            ))
+                .block(Block::default().borders(Borders::ALL).title(" Days "))
+                .wrap(Wrap { trim: false });
+            frame.render_widget(empty, main);
+            frame.render_widget(Line::from(" q: quit"), help);
+            return
+        }
+
+        let [left, right] = Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)]).areas(main);
+
+        let items: Vec<ListItem> = self.solutions.iter().enumerate().map(|(idx, s)| {
+            let mut spans = vec![Span::raw(format!("{} {:>2}  {}", s.year, s.day, s.title))];
+            for star in [Star::One, Star::Two] {
+                spans.push(Span::raw(" "));
+                spans.push(status_span(self.statuses.get(&(idx, Task::Star(star)))));
+            }
+            ListItem::new(Line::from(spans))
+        }).collect();
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(" Days "))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, left, &mut self.list);
+
+        let selected = self.list.selected().unwrap_or(0);
+        let solution = &self.solutions[selected];
+        let mut lines = vec![
+            Line::styled(solution.title, Style::default().add_modifier(Modifier::BOLD)),
+            Line::raw(""),
+            Line::raw(solution.description),
+        ];
+        for star in [Star::One, Star::Two] {
+            lines.push(Line::raw(""));
+            lines.push(Line::styled(format!("Star {}", star.level()), Style::default().add_modifier(Modifier::BOLD)));
+            match self.statuses.get(&(selected, Task::Star(star))) {
+                None => lines.push(Line::raw("Not run yet")),
+                Some(Status::Running) => lines.push(Line::raw("Running...")),
+                Some(Status::Done { answer, elapsed }) => {
+                    lines.push(Line::raw(format!("Took {elapsed:?}")));
+                    match answer {
+                        Ok(a) => lines.extend(a.lines().map(|l| Line::raw(l.to_owned()))),
+                        Err(e) => lines.extend(e.lines().map(|l| Line::styled(l.to_owned(), Color::Red))),
+                    }
+                }
+            }
+        }
+        let details = Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL).title(format!(" Day {} ", solution.day)))
+            .wrap(Wrap { trim: false });
+        frame.render_widget(details, right);
+
+        let viz_help = if solution.visualise.is_some() { "   v: visualise" } else { "" };
+        frame.render_widget(Line::from(format!(" up/down: select   1/2: run star   r: re-run both{viz_help}   q: quit")), help);
+    }
+}
+
+fn status_span(status: Option<&Status>) -> Span<'static> {
+    match status {
+        None => Span::styled("-", Color::DarkGray),
+        Some(Status::Running) => Span::styled("…", Color::Yellow),
+        Some(Status::Done { answer: Ok(_), elapsed }) => Span::styled(format!("✓ {elapsed:.1?}"), Color::Green),
+        Some(Status::Done { answer: Err(_), .. }) => Span::styled("✗", Color::Red),
+    }
+}
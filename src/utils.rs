@@ -2,4 +2,157 @@ macro_rules! regex {
     ($pat:literal) => {{
         regex::Regex::new($pat).expect("valid pattern expected")
     }}
-}
\ No newline at end of file
+}
+
+/// Draw some points on a grid as text, one line per row, covering just the area
+/// that the points span. Anything not given is drawn as `empty`.
+#[cfg(all(feature = "viz", any(feature = "day09", feature = "day12", feature = "day14")))]
+pub fn render_grid(cells: &std::collections::HashMap<(i32,i32), char>, empty: char) -> String {
+    let (Some(min_x), Some(max_x)) = (cells.keys().map(|k| k.0).min(), cells.keys().map(|k| k.0).max()) else {
+        return String::new()
+    };
+    let min_y = cells.keys().map(|k| k.1).min().unwrap_or(0);
+    let max_y = cells.keys().map(|k| k.1).max().unwrap_or(0);
+
+    let mut out = String::new();
+    for y in min_y..=max_y {
+        if y > min_y {
+            out.push('\n');
+        }
+        for x in min_x..=max_x {
+            out.push(*cells.get(&(x,y)).unwrap_or(&empty));
+        }
+    }
+    out
+}
+
+pub fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// Turn a text render (like those from `visualise`) into an SVG, with a square
+/// per character. Background characters are left blank.
+pub fn svg(text: &str) -> String {
+    use std::fmt::Write;
+
+    const CELL: usize = 4;
+    let height = text.lines().count();
+    let width = text.lines().map(|l| l.chars().count()).max().unwrap_or(0);
+
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">\n<rect width=\"100%\" height=\"100%\" fill=\"#f8f8f0\"/>\n",
+        width * CELL, height * CELL, width * CELL, height * CELL
+    );
+    for (y, line) in text.lines().enumerate() {
+        let chars: Vec<char> = line.chars().collect();
+        let mut x = 0;
+        // Draw runs of the same character as one rectangle to keep the size down.
+        while x < chars.len() {
+            let c = chars[x];
+            let run = chars[x..].iter().take_while(|&&o| o == c).count();
+            if let Some(colour) = colour(c) {
+                let _ = writeln!(out, "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{CELL}\" fill=\"{colour}\"/>", x * CELL, y * CELL, run * CELL);
+            }
+            x += run;
+        }
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
+fn colour(c: char) -> Option<&'static str> {
+    Some(match c {
+        '.' | ' ' => return None,
+        '#' => "#333333",
+        'o' => "#d9a441",
+        'S' | 'E' | 's' | '+' => "#d03030",
+        ',' => "#dbe9f6",
+        ':' => "#bad6eb",
+        '-' => "#89bedc",
+        '=' => "#539ecd",
+        '~' => "#2b7bba",
+        '*' => "#0b559f",
+        '%' => "#08306b",
+        '@' => "#041a3a",
+        _ => "#7a7a7a",
+    })
+}
+
+/// Where local bookkeeping (submissions and the like) lives; `AOC_DATA_DIR`
+/// if set, else `.aoc` in the crate root.
+pub fn data_dir() -> std::path::PathBuf {
+    std::env::var_os("AOC_DATA_DIR")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|| std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(".aoc"))
+}
+
+/// Seconds since the unix epoch.
+pub fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// A quick, stable (unlike `DefaultHasher`) hash of some bytes; 64 bit FNV-1a.
+pub fn hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |h, &b| (h ^ b as u64).wrapping_mul(0x100000001b3))
+}
+
+/// A fresh directory for a test to work in, which is removed along with
+/// everything in it when dropped, so even when the test fails.
+#[cfg(test)]
+pub struct TempDir {
+    pub path: std::path::PathBuf,
+}
+
+#[cfg(test)]
+impl TempDir {
+    pub fn new(name: &str) -> TempDir {
+        let path = std::env::temp_dir().join(format!("aoc-{name}-{}", std::process::id()));
+        // In case an earlier run was killed before it could tidy up.
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        TempDir { path }
+    }
+}
+
+#[cfg(test)]
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
+
+/// Accept a single connection on a local port, reply with `response` and hand
+/// back the raw request that was received. Handy as a stand-in for the real site.
+#[cfg(test)]
+pub fn serve_once(response: String) -> (std::net::SocketAddr, std::thread::JoinHandle<String>) {
+    use std::io::{ Read, Write };
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let handle = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut req = Vec::new();
+        let mut buf = [0u8; 4096];
+        // Read the headers, and then however much body they say there is.
+        loop {
+            let n = stream.read(&mut buf).unwrap();
+            req.extend_from_slice(&buf[..n]);
+            let text = String::from_utf8_lossy(&req);
+            if let Some(header_end) = text.find("\r\n\r\n") {
+                let content_length = text[..header_end]
+                    .lines()
+                    .find_map(|l| l.to_lowercase().strip_prefix("content-length:").map(|n| n.trim().parse().unwrap_or(0)))
+                    .unwrap_or(0);
+                if req.len() >= header_end + 4 + content_length || n == 0 {
+                    break
+                }
+            }
+        }
+        write!(stream, "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}", response.len()).unwrap();
+        String::from_utf8_lossy(&req).into_owned()
+    });
+    (addr, handle)
+}
@@ -0,0 +1,367 @@
+use crate::{ File, params };
+use crate::rng::Rng;
+use crate::solution::Extra;
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::io::BufRead;
+
+pub const EXTRAS: &[Extra] = &[
+    Extra { name: "top", description: "The elves carrying the most calories (--top N, default 3).", run: top_report },
+    Extra { name: "stats", description: "Everything about what the elves carry (--format text|json).", run: stats_report },
+];
+
+pub fn star1(file: File) -> Result<usize, anyhow::Error> {
+    stream1(&mut file.contents.as_bytes())
+}
+
+pub fn star2(file: File) -> Result<usize, anyhow::Error> {
+    stream2(&mut file.contents.as_bytes())
+}
+
+/// Star 1 read as it goes, so only one elf is ever held in memory.
+pub fn stream1(input: &mut dyn BufRead) -> Result<usize, anyhow::Error> {
+    Ok(top(Elves::new(input), 1)?.first().map_or(0, |elf| elf.calories))
+}
+
+/// Star 2 read as it goes, holding on to just the best few elves.
+pub fn stream2(input: &mut dyn BufRead) -> Result<usize, anyhow::Error> {
+    total(&top(Elves::new(input), top_count()?)?)
+}
+
+/// All the calories some elves carry between them.
+fn total(elves: &[Elf]) -> Result<usize, anyhow::Error> {
+    elves.iter()
+        .try_fold(0usize, |total, elf| total.checked_add(elf.calories))
+        .ok_or_else(|| anyhow::anyhow!("Total calories overflow"))
+}
+
+/// An elf, numbered from 1 in the order they appear, and what they're carrying.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Elf {
+    pub index: usize,
+    pub calories: usize,
+    pub items: usize,
+}
+
+/// How many of the best elves to add up in star 2.
+fn top_count() -> Result<usize, anyhow::Error> {
+    params::get("2022.day01.top", 3)
+}
+
+/// The `k` elves carrying the most, most first (and earlier elves first when
+/// they carry the same). Only `k` elves are kept at a time, in a min-heap with
+/// the worst of them on top, ready to be pushed out by a better one.
+pub fn top(elves: impl IntoIterator<Item = Result<Elf, anyhow::Error>>, k: usize) -> Result<Vec<Elf>, anyhow::Error> {
+    let mut best = BinaryHeap::with_capacity(k + 1);
+    for elf in elves {
+        let elf = elf?;
+        best.push(Reverse((elf.calories, Reverse(elf.index), elf.items)));
+        if best.len() > k {
+            best.pop();
+        }
+    }
+    // Sorting the `Reverse`d elves ascending puts the best first.
+    Ok(best
+        .into_sorted_vec()
+        .into_iter()
+        .map(|Reverse((calories, Reverse(index), items))| Elf { index, calories, items })
+        .collect())
+}
+
+fn top_report(file: File) -> Result<String, anyhow::Error> {
+    let top = top(Elves::new(file.contents.as_bytes()), top_count()?)?;
+    let mut out: Vec<String> = top
+        .iter()
+        .enumerate()
+        .map(|(n, elf)| format!("{}. Elf {}: {}", n + 1, elf.index, elf.calories))
+        .collect();
+    out.push(format!("Total: {}", total(&top)?));
+    Ok(out.join("\n"))
+}
+
+/// Everything there is to know about what the elves are carrying.
+#[derive(Debug, Serialize)]
+struct Inventory {
+    elves: usize,
+    items: usize,
+    total_calories: usize,
+    mean: f64,
+    median: f64,
+    /// The population standard deviation.
+    std_dev: f64,
+    per_elf: Vec<Elf>,
+    histogram: Vec<Bin>,
+    /// Every elf carrying the most, in case there's a tie.
+    top: Vec<Elf>,
+}
+
+/// How many elves carry from `from` to `to` calories, inclusive.
+#[derive(Debug, PartialEq, Serialize)]
+struct Bin {
+    from: usize,
+    to: usize,
+    count: usize,
+}
+
+impl Inventory {
+    const BINS: usize = 10;
+
+    /// Takes stock in one go through the input, keeping a running mean and
+    /// variance (Welford's method) along with each elf.
+    fn take(input: &str) -> Result<Inventory, anyhow::Error> {
+        let (mut mean, mut m2) = (0.0, 0.0);
+        let mut per_elf = Vec::new();
+        for elf in Elves::new(input.as_bytes()) {
+            let elf = elf?;
+            per_elf.push(elf);
+            let delta = elf.calories as f64 - mean;
+            mean += delta / per_elf.len() as f64;
+            m2 += delta * (elf.calories as f64 - mean);
+        }
+
+        let mut sorted: Vec<usize> = per_elf.iter().map(|elf| elf.calories).collect();
+        sorted.sort_unstable();
+        let median = match sorted.len() {
+            0 => 0.0,
+            n if n % 2 == 1 => sorted[n / 2] as f64,
+            n => (sorted[n / 2 - 1] as f64 + sorted[n / 2] as f64) / 2.0,
+        };
+        let most = sorted.last().copied();
+
+        Ok(Inventory {
+            elves: per_elf.len(),
+            items: per_elf.iter().map(|elf| elf.items).sum(),
+            total_calories: total(&per_elf)?,
+            mean,
+            median,
+            std_dev: if per_elf.is_empty() { 0.0 } else { (m2 / per_elf.len() as f64).sqrt() },
+            histogram: histogram(&sorted, Self::BINS),
+            top: per_elf.iter().filter(|elf| Some(elf.calories) == most).copied().collect(),
+            per_elf,
+        })
+    }
+
+    fn to_text(&self) -> String {
+        let mut out = vec![
+            format!("Elves: {}", self.elves),
+            format!("Items: {}", self.items),
+            format!("Calories: {} in total, mean {:.1}, median {:.1}, standard deviation {:.1}", self.total_calories, self.mean, self.median, self.std_dev),
+        ];
+        if let Some(first) = self.top.first() {
+            let who: Vec<String> = self.top.iter().map(|elf| elf.index.to_string()).collect();
+            let tie = if self.top.len() > 1 { " (a tie)" } else { "" };
+            out.push(format!("Most carried: {} by elf {}{tie}", first.calories, who.join(", ")));
+        }
+
+        out.push(String::new());
+        out.extend(self.per_elf.iter().map(|elf| {
+            let items = if elf.items == 1 { "item" } else { "items" };
+            format!("Elf {}: {} calories in {} {items}", elf.index, elf.calories, elf.items)
+        }));
+
+        out.push(String::new());
+        let widest = self.histogram.iter().map(|bin| bin.count).max().unwrap_or(0).max(1);
+        let digits = self.histogram.last().map_or(1, |bin| bin.to.to_string().len());
+        out.extend(self.histogram.iter().map(|bin| {
+            let bar = "#".repeat((bin.count * 40).div_ceil(widest));
+            format!("{:>digits$} - {:>digits$} | {bar} {}", bin.from, bin.to, bin.count)
+        }));
+        out.join("\n")
+    }
+}
+
+/// Split the range of `sorted` values into (up to) `bins` equally wide bins
+/// and count how many are in each.
+fn histogram(sorted: &[usize], bins: usize) -> Vec<Bin> {
+    let (Some(&min), Some(&max)) = (sorted.first(), sorted.last()) else {
+        return Vec::new()
+    };
+    // The same as (max - min + 1).div_ceil(bins), without overflowing when the
+    // values cover everything a usize can be.
+    let width = (max - min) / bins + 1;
+    let mut histogram: Vec<Bin> = (0..bins)
+        .map(|n| Bin { from: min + n * width, to: (min + n * width).saturating_add(width - 1), count: 0 })
+        .take_while(|bin| bin.from <= max)
+        .collect();
+    for value in sorted {
+        histogram[(value - min) / width].count += 1;
+    }
+    histogram
+}
+
+fn stats_report(file: File) -> Result<String, anyhow::Error> {
+    let inventory = Inventory::take(&file.contents)?;
+    let format: String = params::get("2022.day01.format", "text".to_owned())?;
+    match format.as_str() {
+        "text" => Ok(inventory.to_text()),
+        "json" => Ok(serde_json::to_string_pretty(&inventory)?),
+        other => anyhow::bail!("Unknown format '{other}'; expecting 'text' or 'json'"),
+    }
+}
+
+#[cfg(test)]
+fn parse_input(input: &str) -> Result<Vec<Elf>, anyhow::Error> {
+    Elves::new(input.as_bytes()).collect()
+}
+
+/// Reads each elf in turn, whether or not the last one is followed by a blank
+/// line. Lines are read into the one buffer, so any amount of input can go
+/// through without holding on to it.
+struct Elves<R> {
+    input: R,
+    buf: String,
+    line: usize,
+    index: usize,
+}
+
+impl<R: BufRead> Elves<R> {
+    fn new(input: R) -> Self {
+        Elves { input, buf: String::new(), line: 0, index: 0 }
+    }
+}
+
+impl<R: BufRead> Iterator for Elves<R> {
+    type Item = Result<Elf, anyhow::Error>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut calories: Option<usize> = None;
+        let mut items = 0;
+        loop {
+            self.buf.clear();
+            match self.input.read_line(&mut self.buf) {
+                Ok(0) => break,
+                Ok(_) => {},
+                Err(e) => return Some(Err(anyhow::anyhow!("Line {}: {e}", self.line + 1))),
+            }
+            self.line += 1;
+            let line = self.buf.trim();
+            if line.is_empty() {
+                if calories.is_some() {
+                    break
+                }
+                continue
+            }
+            let Ok(c) = line.parse::<usize>() else {
+                return Some(Err(anyhow::anyhow!("Line {}: '{line}' isn't a number of calories", self.line)))
+            };
+            match calories.unwrap_or(0).checked_add(c) {
+                Some(total) => calories = Some(total),
+                None => return Some(Err(anyhow::anyhow!("Line {}: total calories overflow", self.line))),
+            }
+            items += 1;
+        }
+        let calories = calories?;
+        self.index += 1;
+        Some(Ok(Elf { index: self.index, calories, items }))
+    }
+}
+
+/// Star 1 the simple way, to check the real one against.
+pub fn reference1(file: File) -> Result<usize, anyhow::Error> {
+    Ok(reference_totals(&file.contents)?.first().copied().unwrap_or(0))
+}
+
+/// Star 2 the simple way, to check the real one against.
+pub fn reference2(file: File) -> Result<usize, anyhow::Error> {
+    reference_totals(&file.contents)?
+        .iter()
+        .take(top_count()?)
+        .try_fold(0usize, |total, c| total.checked_add(*c))
+        .ok_or_else(|| anyhow::anyhow!("Total calories overflow"))
+}
+
+/// Every elf's total, biggest first.
+fn reference_totals(input: &str) -> Result<Vec<usize>, anyhow::Error> {
+    let mut totals = input
+        .trim()
+        .split("\n\n")
+        .filter(|elf| !elf.trim().is_empty())
+        .map(|elf| elf.lines().try_fold(0usize, |total, l| {
+            total.checked_add(l.trim().parse()?).ok_or_else(|| anyhow::anyhow!("Total calories overflow"))
+        }))
+        .collect::<Result<Vec<usize>, anyhow::Error>>()?;
+    totals.sort_by(|a, b| b.cmp(a));
+    Ok(totals)
+}
+
+/// `size` elves, each carrying a handful of snacks.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let elves: Vec<String> = (0..size.max(1))
+        .map(|_| (0..rng.range(1, 15)).map(|_| format!("{}\n", rng.range(1000, 70000))).collect())
+        .collect();
+    elves.join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000";
+
+    #[test]
+    fn example() {
+        assert_eq!(star1(File { contents: EXAMPLE.to_string() }).unwrap(), 24000);
+        assert_eq!(star2(File { contents: EXAMPLE.to_string() }).unwrap(), 45000);
+    }
+
+    #[test]
+    fn top_elves() {
+        let elves = parse_input(EXAMPLE).unwrap();
+        // The last elf counts even without a blank line after them.
+        assert_eq!(elves.len(), 5);
+        let top = |k| top(elves.iter().copied().map(Ok), k).unwrap();
+        assert_eq!(top(2), [Elf { index: 4, calories: 24000, items: 3 }, Elf { index: 3, calories: 11000, items: 2 }]);
+        assert_eq!(top(10).len(), 5);
+        assert!(top(0).is_empty());
+
+        // Ties go to whoever came first, however far apart they are.
+        let tied = format!("{EXAMPLE}\n\n24000\n\n1\n");
+        let best = super::top(Elves::new(tied.as_bytes()), 2).unwrap();
+        assert_eq!(best.iter().map(|elf| elf.index).collect::<Vec<_>>(), [4, 6]);
+
+        params::with(&[("2022.day01.top", "1")], || {
+            assert_eq!(star2(File { contents: EXAMPLE.to_string() }).unwrap(), 24000);
+        });
+    }
+
+    #[test]
+    fn inventory() {
+        let inventory = Inventory::take(&format!("{EXAMPLE}\n\n24000\n")).unwrap();
+        assert_eq!((inventory.elves, inventory.items, inventory.total_calories), (6, 11, 79000));
+        assert!((inventory.mean - 79000.0 / 6.0).abs() < 1e-9);
+        assert_eq!(inventory.median, 10500.0);
+        let variance = [6000.0, 4000.0, 11000.0, 24000.0, 10000.0, 24000.0]
+            .iter()
+            .map(|c: &f64| (c - inventory.mean).powi(2))
+            .sum::<f64>() / 6.0;
+        assert!((inventory.std_dev - variance.sqrt()).abs() < 1e-9);
+        assert_eq!(inventory.top.iter().map(|elf| elf.index).collect::<Vec<_>>(), [4, 6]);
+        assert_eq!(inventory.histogram.iter().map(|bin| bin.count).sum::<usize>(), 6);
+        assert_eq!(inventory.histogram[0], Bin { from: 4000, to: 6000, count: 2 });
+    }
+
+    #[test]
+    fn bad_lines() {
+        let err = star1(File { contents: "1000\n\n2000\nlots\n".to_string() }).unwrap_err();
+        assert_eq!(err.to_string(), "Line 4: 'lots' isn't a number of calories");
+    }
+
+    #[test]
+    fn too_many_calories() {
+        let max = usize::MAX;
+        let err = star1(File { contents: format!("{max}\n1\n") }).unwrap_err();
+        assert_eq!(err.to_string(), "Line 2: total calories overflow");
+        // Each elf fits, but not all of the top three together.
+        let input = format!("{max}\n\n{max}\n\n1\n");
+        assert_eq!(star1(File { contents: input.clone() }).unwrap(), max);
+        assert_eq!(star2(File { contents: input.clone() }).unwrap_err().to_string(), "Total calories overflow");
+        assert!(reference2(File { contents: input.clone() }).is_err());
+        assert!(Inventory::take(&input).is_err());
+
+        // Elves carrying anything from nothing to everything still make a histogram.
+        let inventory = Inventory::take(&format!("0\n\n{max}\n")).unwrap();
+        assert_eq!(inventory.histogram.first().unwrap().from, 0);
+        assert_eq!(inventory.histogram.last().unwrap().to, max);
+        assert_eq!(inventory.histogram.iter().map(|bin| bin.count).sum::<usize>(), 2);
+    }
+}
@@ -0,0 +1,576 @@
+use crate::{ File, params };
+use crate::rng::Rng;
+use crate::solution::Extra;
+use itertools::Itertools;
+use serde::Deserialize;
+use std::fmt;
+
+pub const EXTRAS: &[Extra] = &[
+    Extra { name: "ciphers", description: "Score every way the second column could map onto shapes or outcomes.", run: cipher_report },
+    Extra { name: "rounds", description: "Round by round scores (--star 1|2, --format table|csv).", run: rounds_report },
+];
+
+pub fn parse(input: &str) -> Result<(), anyhow::Error> {
+    parse_input(&rules()?, input).count();
+    Ok(())
+}
+
+/// A round of the strategy guide: the shape they play, and the letter we're
+/// told, which means either a shape (star 1) or an outcome (star 2).
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Round {
+    line: usize,
+    them: usize,
+    column: char,
+}
+
+/// Each round of the strategy guide, or why a line isn't one. Blank lines are
+/// skipped, and the stars skip any lines they can't make sense of too.
+fn parse_input<'a>(rules: &'a Rules, input: &'a str) -> impl Iterator<Item = Result<Round, String>> + 'a {
+    input.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()).map(|(n, l)| {
+        let round = || {
+            let mut cs = l.trim().split(|c: char| c.is_whitespace());
+            let them = rules.opponent(letter(cs.next()?)?)?;
+            let column = letter(cs.next()?).filter(|&c| rules.player(c).is_some() || rules.outcome(c).is_some())?;
+            Some(Round { line: n + 1, them, column })
+        };
+        round().ok_or_else(|| format!("Line {}: '{}' isn't a round", n + 1, l.trim()))
+    })
+}
+
+/// The only character in a string, if there's just the one.
+fn letter(s: &str) -> Option<char> {
+    let mut chars = s.trim().chars();
+    chars.next().filter(|_| chars.next().is_none())
+}
+
+pub fn star1(file: File) -> Result<usize, anyhow::Error> {
+    let rules = rules()?;
+    let score = parse_input(&rules, &file.contents)
+        .filter_map(Result::ok)
+        .filter_map(|round| Some(rules.score(rules.player(round.column)?, round.them)))
+        .sum();
+    Ok(score)
+}
+
+pub fn star2(file: File) -> Result<usize, anyhow::Error> {
+    let rules = rules()?;
+    let mut score = 0;
+    for round in parse_input(&rules, &file.contents).filter_map(Result::ok) {
+        let Some(outcome) = rules.outcome(round.column) else { continue };
+        score += rules.score(play_for(&rules, round.them, outcome)?, round.them);
+    }
+    Ok(score)
+}
+
+/// The shape to play against `them` to get `outcome`, or an error if there's none.
+fn play_for(rules: &Rules, them: usize, outcome: Outcome) -> Result<usize, anyhow::Error> {
+    rules.shape_for(them, outcome)
+        .ok_or_else(|| anyhow::anyhow!("Nothing can {outcome} against {}", rules.shapes[them].name))
+}
+
+/// How a round can go, for us.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    Lose,
+    Draw,
+    Win,
+}
+
+impl Outcome {
+    pub const ALL: [Outcome; 3] = [Outcome::Lose, Outcome::Draw, Outcome::Win];
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Outcome::Lose => "lose",
+            Outcome::Draw => "draw",
+            Outcome::Win => "win",
+        })
+    }
+}
+
+/// The rules as written down: each shape, with the letters it goes by, what it
+/// scores and what it beats, and the letter and score for each outcome.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RulesConfig {
+    shapes: Vec<ShapeConfig>,
+    outcomes: OutcomesConfig,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ShapeConfig {
+    name: String,
+    opponent: char,
+    player: char,
+    score: usize,
+    #[serde(default)]
+    beats: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct OutcomesConfig {
+    lose: OutcomeConfig,
+    draw: OutcomeConfig,
+    win: OutcomeConfig,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct OutcomeConfig {
+    letter: char,
+    score: usize,
+}
+
+/// The game the strategy guide is for, checked over and ready to score rounds.
+#[derive(Debug)]
+pub struct Rules {
+    pub shapes: Vec<Shape>,
+    /// `beats[a][b]` when shape `a` beats shape `b`.
+    beats: Vec<Vec<bool>>,
+    /// The letter and score of each outcome, in the order of `Outcome::ALL`.
+    outcomes: [(char, usize); 3],
+}
+
+#[derive(Debug)]
+pub struct Shape {
+    pub name: String,
+    pub opponent: char,
+    pub player: char,
+    pub score: usize,
+}
+
+/// The games built in, by name.
+const PRESETS: &[(&str, &str)] = &[("classic", CLASSIC), ("rpsls", RPSLS)];
+
+const CLASSIC: &str = r#"
+shapes = [
+    { name = "Rock", opponent = "A", player = "X", score = 1, beats = ["Scissors"] },
+    { name = "Paper", opponent = "B", player = "Y", score = 2, beats = ["Rock"] },
+    { name = "Scissors", opponent = "C", player = "Z", score = 3, beats = ["Paper"] },
+]
+
+[outcomes]
+lose = { letter = "X", score = 0 }
+draw = { letter = "Y", score = 3 }
+win = { letter = "Z", score = 6 }
+"#;
+
+/// Rock Paper Scissors Lizard Spock, where each shape beats two others.
+const RPSLS: &str = r#"
+shapes = [
+    { name = "Rock", opponent = "A", player = "X", score = 1, beats = ["Scissors", "Lizard"] },
+    { name = "Paper", opponent = "B", player = "Y", score = 2, beats = ["Rock", "Spock"] },
+    { name = "Scissors", opponent = "C", player = "Z", score = 3, beats = ["Paper", "Lizard"] },
+    { name = "Lizard", opponent = "D", player = "W", score = 4, beats = ["Paper", "Spock"] },
+    { name = "Spock", opponent = "E", player = "V", score = 5, beats = ["Rock", "Scissors"] },
+]
+
+[outcomes]
+lose = { letter = "X", score = 0 }
+draw = { letter = "Y", score = 3 }
+win = { letter = "Z", score = 6 }
+"#;
+
+/// The rules to play by: a preset named by the `2022.day02.rules` param, or
+/// else a TOML file at that path laid out like the presets.
+fn rules() -> Result<Rules, anyhow::Error> {
+    let name: String = params::get("2022.day02.rules", "classic".to_owned())?;
+    if let Some((_, preset)) = PRESETS.iter().find(|(preset, _)| *preset == name) {
+        return Rules::from_toml(preset)
+    }
+    let contents = std::fs::read_to_string(&name).map_err(|e| {
+        let presets: Vec<&str> = PRESETS.iter().map(|(name, _)| *name).collect();
+        anyhow::anyhow!("Rules '{name}' aren't a preset ({}) or a file we can read: {e}", presets.join(", "))
+    })?;
+    Rules::from_toml(&contents).map_err(|e| anyhow::anyhow!("{name}: {e}"))
+}
+
+impl Rules {
+    pub fn from_toml(contents: &str) -> Result<Rules, anyhow::Error> {
+        let config: RulesConfig = toml::from_str(contents)?;
+        Rules::new(config)
+    }
+
+    fn new(config: RulesConfig) -> Result<Rules, anyhow::Error> {
+        let shapes = &config.shapes;
+        if shapes.is_empty() {
+            anyhow::bail!("There need to be some shapes to play");
+        }
+        for (n, shape) in shapes.iter().enumerate() {
+            for other in &shapes[..n] {
+                if other.name == shape.name {
+                    anyhow::bail!("There are two shapes called {}", shape.name);
+                }
+                if other.opponent == shape.opponent || other.player == shape.player {
+                    anyhow::bail!("{} and {} go by the same letter", other.name, shape.name);
+                }
+            }
+        }
+
+        let mut beats = vec![vec![false; shapes.len()]; shapes.len()];
+        for (a, shape) in shapes.iter().enumerate() {
+            for name in &shape.beats {
+                let b = shapes.iter().position(|s| s.name == *name)
+                    .ok_or_else(|| anyhow::anyhow!("{} beats {name}, which isn't a shape", shape.name))?;
+                if a == b {
+                    anyhow::bail!("{} can't beat itself", shape.name);
+                }
+                beats[a][b] = true;
+            }
+        }
+        for a in 0..shapes.len() {
+            for b in 0..a {
+                if beats[a][b] && beats[b][a] {
+                    anyhow::bail!("{} and {} can't both beat each other", shapes[b].name, shapes[a].name);
+                }
+            }
+        }
+
+        let OutcomesConfig { lose, draw, win } = config.outcomes;
+        let outcomes = [(lose.letter, lose.score), (draw.letter, draw.score), (win.letter, win.score)];
+        if outcomes[0].0 == outcomes[1].0 || outcomes[1].0 == outcomes[2].0 || outcomes[0].0 == outcomes[2].0 {
+            anyhow::bail!("Each outcome needs a letter of its own");
+        }
+
+        let shapes = config.shapes.into_iter()
+            .map(|s| Shape { name: s.name, opponent: s.opponent, player: s.player, score: s.score })
+            .collect();
+        Ok(Rules { shapes, beats, outcomes })
+    }
+
+    /// The letters that can be in the second column: those of the outcomes,
+    /// which for the usual games are the same as the shapes we play.
+    fn columns(&self) -> Vec<char> {
+        self.outcomes.iter().map(|(letter, _)| *letter).collect()
+    }
+
+    /// The shape they play for a letter in the first column.
+    pub fn opponent(&self, letter: char) -> Option<usize> {
+        self.shapes.iter().position(|s| s.opponent == letter)
+    }
+
+    /// The shape we play for a letter in the second column, taking it to mean a shape.
+    pub fn player(&self, letter: char) -> Option<usize> {
+        self.shapes.iter().position(|s| s.player == letter)
+    }
+
+    /// The outcome we want for a letter in the second column, taking it to mean an outcome.
+    pub fn outcome(&self, letter: char) -> Option<Outcome> {
+        Outcome::ALL.into_iter().zip(self.outcomes).find(|(_, (l, _))| *l == letter).map(|(outcome, _)| outcome)
+    }
+
+    /// How it goes for us playing `me` against `them`. Shapes that don't beat
+    /// each other either way draw.
+    pub fn play(&self, me: usize, them: usize) -> Outcome {
+        if self.beats[me][them] {
+            Outcome::Win
+        } else if self.beats[them][me] {
+            Outcome::Lose
+        } else {
+            Outcome::Draw
+        }
+    }
+
+    pub fn outcome_score(&self, outcome: Outcome) -> usize {
+        self.outcomes[outcome as usize].1
+    }
+
+    /// What we score for a round.
+    pub fn score(&self, me: usize, them: usize) -> usize {
+        self.shapes[me].score + self.outcome_score(self.play(me, them))
+    }
+
+    /// A shape to play against `them` to get `outcome`: their own shape for a
+    /// draw, and otherwise the first that does it, in the order they're listed.
+    pub fn shape_for(&self, them: usize, outcome: Outcome) -> Option<usize> {
+        std::iter::once(them).chain(0..self.shapes.len()).find(|&me| self.play(me, them) == outcome)
+    }
+}
+
+/// One way of reading the letters of the second column (in the order of
+/// `Rules::columns`): as the shapes we play, or as the outcomes we want.
+#[derive(Debug, Clone, PartialEq)]
+enum Cipher {
+    Shapes(Vec<usize>),
+    Outcomes(Vec<Outcome>),
+}
+
+impl Cipher {
+    /// Every way of mapping the second column's letters onto different shapes,
+    /// and then onto different outcomes.
+    fn all(rules: &Rules) -> Vec<Cipher> {
+        let letters = rules.columns().len();
+        let shapes = (0..rules.shapes.len()).permutations(letters).map(Cipher::Shapes);
+        let outcomes = Outcome::ALL.into_iter().permutations(letters).map(Cipher::Outcomes);
+        shapes.chain(outcomes).collect()
+    }
+
+    /// What following the guide read this way scores. Rounds whose letter
+    /// isn't one of the columns are skipped, as the stars do.
+    fn score(&self, rules: &Rules, rounds: &[Round]) -> Result<usize, anyhow::Error> {
+        let columns = rules.columns();
+        let mut total = 0;
+        for round in rounds {
+            let Some(n) = columns.iter().position(|&c| c == round.column) else { continue };
+            let me = match self {
+                Cipher::Shapes(shapes) => shapes[n],
+                Cipher::Outcomes(outcomes) => play_for(rules, round.them, outcomes[n])?,
+            };
+            total += rules.score(me, round.them);
+        }
+        Ok(total)
+    }
+
+    /// Like "X=Rock, Y=Paper, Z=Scissors".
+    fn describe(&self, rules: &Rules) -> String {
+        let meanings: Vec<String> = match self {
+            Cipher::Shapes(shapes) => shapes.iter().map(|&s| rules.shapes[s].name.clone()).collect(),
+            Cipher::Outcomes(outcomes) => outcomes.iter().map(|o| o.to_string()).collect(),
+        };
+        rules.columns().iter().zip(meanings).map(|(c, m)| format!("{c}={m}")).join(", ")
+    }
+
+    /// Which star reads the guide this way, if either does.
+    fn star(&self, rules: &Rules) -> Option<u8> {
+        let columns = rules.columns();
+        match self {
+            Cipher::Shapes(shapes) => shapes.iter().zip(&columns).all(|(&s, &c)| rules.player(c) == Some(s)).then_some(1),
+            Cipher::Outcomes(outcomes) => outcomes.iter().zip(&columns).all(|(&o, &c)| rules.outcome(c) == Some(o)).then_some(2),
+        }
+    }
+}
+
+/// Every cipher with its score, best first (and in the order `Cipher::all`
+/// gives them when they score the same).
+fn rank_ciphers(rules: &Rules, input: &str) -> Result<Vec<(Cipher, usize)>, anyhow::Error> {
+    let rounds: Vec<Round> = parse_input(rules, input).filter_map(Result::ok).collect();
+    let mut ranked = Cipher::all(rules)
+        .into_iter()
+        .map(|cipher| cipher.score(rules, &rounds).map(|total| (cipher, total)))
+        .collect::<Result<Vec<_>, _>>()?;
+    ranked.sort_by_key(|(_, total)| std::cmp::Reverse(*total));
+    Ok(ranked)
+}
+
+fn cipher_report(file: File) -> Result<String, anyhow::Error> {
+    let rules = rules()?;
+    let ranked = rank_ciphers(&rules, &file.contents)?;
+    let (Some((best, most)), Some((worst, least))) = (ranked.first(), ranked.last()) else {
+        anyhow::bail!("There's no way to read the second column with these rules");
+    };
+
+    let digits = most.to_string().len();
+    let mut out: Vec<String> = ranked.iter().enumerate().map(|(n, (cipher, total))| {
+        let star = cipher.star(&rules).map(|star| format!(" (star {star})")).unwrap_or_default();
+        format!("{:>3}. {total:>digits$}  {}{star}", n + 1, cipher.describe(&rules))
+    }).collect();
+    out.push(String::new());
+    out.push(format!("Best: {most} with {}", best.describe(&rules)));
+    out.push(format!("Worst: {least} with {}", worst.describe(&rules)));
+    Ok(out.join("\n"))
+}
+
+/// How one round went, following the guide as one of the stars reads it.
+#[derive(Debug, PartialEq)]
+struct Played {
+    round: usize,
+    line: usize,
+    them: usize,
+    me: usize,
+    outcome: Outcome,
+    shape_score: usize,
+    outcome_score: usize,
+    total: usize,
+}
+
+/// Play the guide through as star 1 or 2 reads it. Lines that aren't rounds,
+/// or whose letter means nothing to that star, come back as errors in place.
+fn play_through(rules: &Rules, input: &str, star: u8) -> Result<Vec<Result<Played, String>>, anyhow::Error> {
+    let mut played = Vec::new();
+    let (mut rounds, mut total) = (0, 0);
+    for round in parse_input(rules, input) {
+        let round = match round {
+            Ok(round) => round,
+            Err(e) => {
+                played.push(Err(e));
+                continue
+            }
+        };
+        let me = match star {
+            1 => rules.player(round.column),
+            _ => rules.outcome(round.column).map(|outcome| play_for(rules, round.them, outcome)).transpose()?,
+        };
+        let Some(me) = me else {
+            played.push(Err(format!("Line {}: '{}' doesn't mean anything in star {star}", round.line, round.column)));
+            continue
+        };
+        let outcome = rules.play(me, round.them);
+        let (shape_score, outcome_score) = (rules.shapes[me].score, rules.outcome_score(outcome));
+        rounds += 1;
+        total += shape_score + outcome_score;
+        played.push(Ok(Played { round: rounds, line: round.line, them: round.them, me, outcome, shape_score, outcome_score, total }));
+    }
+    Ok(played)
+}
+
+fn rounds_report(file: File) -> Result<String, anyhow::Error> {
+    let rules = rules()?;
+    let star: u8 = params::get("2022.day02.star", 1)?;
+    if star != 1 && star != 2 {
+        anyhow::bail!("There's no star {star}; expecting 1 or 2");
+    }
+    let played = play_through(&rules, &file.contents, star)?;
+    let (rounds, skipped): (Vec<_>, Vec<_>) = played.into_iter().partition(Result::is_ok);
+    let rounds: Vec<Played> = rounds.into_iter().map(Result::unwrap).collect();
+    let skipped: Vec<String> = skipped.into_iter().map(Result::unwrap_err).collect();
+
+    let format: String = params::get("2022.day02.format", "table".to_owned())?;
+    let name = |shape: usize| rules.shapes[shape].name.as_str();
+    let mut out = Vec::new();
+    match format.as_str() {
+        "table" => {
+            let w = rules.shapes.iter().map(|s| s.name.len()).max().unwrap_or(0).max("Them".len());
+            out.push(format!("Round  {:w$}  {:w$}  Outcome  For shape  For outcome  Total", "Them", "Us"));
+            out.extend(rounds.iter().map(|p| format!(
+                "{:>5}  {:w$}  {:w$}  {:7}  {:>9}  {:>11}  {:>5}",
+                p.round, name(p.them), name(p.me), p.outcome, p.shape_score, p.outcome_score, p.total
+            )));
+        },
+        "csv" => {
+            out.push("round,line,them,us,outcome,shape_score,outcome_score,total".to_owned());
+            out.extend(rounds.iter().map(|p| format!(
+                "{},{},{},{},{},{},{},{}",
+                p.round, p.line, name(p.them), name(p.me), p.outcome, p.shape_score, p.outcome_score, p.total
+            )));
+        },
+        other => anyhow::bail!("Unknown format '{other}'; expecting 'table' or 'csv'"),
+    }
+
+    let count = |outcome| rounds.iter().filter(|p| p.outcome == outcome).count();
+    let summary = format!(
+        "Wins: {}, draws: {}, losses: {}, for a total of {}",
+        count(Outcome::Win), count(Outcome::Draw), count(Outcome::Lose), rounds.last().map_or(0, |p| p.total)
+    );
+    // In CSV, as comments after the rows, which CSV readers can be told to skip.
+    let (gap, prefix) = if format == "csv" { (None, "# ") } else { (Some(String::new()), "") };
+    out.extend(gap);
+    out.push(format!("{prefix}{summary}"));
+    out.extend(skipped.iter().map(|line| format!("{prefix}Skipped: {line}")));
+    Ok(out.join("\n"))
+}
+
+/// `size` rounds of the strategy guide, using the letters in the second
+/// column that mean something in both stars.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let Ok(rules) = rules() else { return String::new() };
+    let theirs: Vec<char> = rules.shapes.iter().map(|s| s.opponent).collect();
+    let ours: Vec<char> = rules.shapes.iter().map(|s| s.player).filter(|&c| rules.outcome(c).is_some()).collect();
+    if ours.is_empty() {
+        return String::new()
+    }
+    (0..size.max(1)).map(|_| format!("{} {}\n", rng.pick(&theirs), rng.pick(&ours))).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "A Y\nB X\nC Z\n";
+
+    #[test]
+    fn example() {
+        assert_eq!(star1(File { contents: EXAMPLE.to_string() }).unwrap(), 15);
+        assert_eq!(star2(File { contents: EXAMPLE.to_string() }).unwrap(), 12);
+    }
+
+    #[test]
+    fn lizard_spock() {
+        params::with(&[("2022.day02.rules", "rpsls")], || {
+            // Spock (5) vaporizes Rock, Lizard (4) poisons Spock, and Rock (1) crushes Lizard.
+            let input = "A V\nE W\nD X\n";
+            assert_eq!(star1(File { contents: input.to_string() }).unwrap(), 11 + 10 + 7);
+            // Lose to Rock with Scissors (the first listed that does), draw Spock,
+            // and beat Lizard with Rock.
+            let input = "A X\nE Y\nD Z\n";
+            assert_eq!(star2(File { contents: input.to_string() }).unwrap(), 3 + 8 + 7);
+        });
+    }
+
+    #[test]
+    fn rounds() {
+        let rules = Rules::from_toml(CLASSIC).unwrap();
+        let input = format!("{EXAMPLE}\nA\n\nQ X\n");
+        let played = play_through(&rules, &input, 2).unwrap();
+        assert_eq!(played.len(), 5);
+        assert_eq!(played[1], Ok(Played {
+            round: 2, line: 2, them: 1, me: 0, outcome: Outcome::Lose, shape_score: 1, outcome_score: 0, total: 5,
+        }));
+        assert_eq!(played[2].as_ref().unwrap().total, 12);
+        assert_eq!(played[3], Err("Line 5: 'A' isn't a round".to_owned()));
+        assert_eq!(played[4], Err("Line 7: 'Q X' isn't a round".to_owned()));
+
+        let rules = Rules::from_toml(RPSLS).unwrap();
+        let played = play_through(&rules, "A V\n", 2).unwrap();
+        assert_eq!(played, [Err("Line 1: 'V' doesn't mean anything in star 2".to_owned())]);
+    }
+
+    #[test]
+    fn rounds_reports() {
+        let file = || File { contents: "A Y\nB X\nfoo\n".to_string() };
+        let table = rounds_report(file()).unwrap();
+        assert_eq!(table, "\
+            Round  Them      Us        Outcome  For shape  For outcome  Total\n\
+            \x20   1  Rock      Paper     win              2            6      8\n\
+            \x20   2  Paper     Rock      lose             1            0      9\n\
+            \n\
+            Wins: 1, draws: 0, losses: 1, for a total of 9\n\
+            Skipped: Line 3: 'foo' isn't a round");
+
+        let csv = params::with(&[("2022.day02.format", "csv"), ("2022.day02.star", "2")], || rounds_report(file())).unwrap();
+        assert_eq!(csv, "\
+            round,line,them,us,outcome,shape_score,outcome_score,total\n\
+            1,1,Rock,Rock,draw,1,3,4\n\
+            2,2,Paper,Rock,lose,1,0,5\n\
+            # Wins: 0, draws: 1, losses: 1, for a total of 5\n\
+            # Skipped: Line 3: 'foo' isn't a round");
+    }
+
+    #[test]
+    fn ciphers() {
+        let rules = Rules::from_toml(CLASSIC).unwrap();
+        let ranked = rank_ciphers(&rules, EXAMPLE).unwrap();
+        // Every ordering of the three shapes, and of the three outcomes.
+        assert_eq!(ranked.len(), 12);
+        assert!(ranked.windows(2).all(|w| w[0].1 >= w[1].1));
+        let star = |n| ranked.iter().find(|(cipher, _)| cipher.star(&rules) == Some(n)).unwrap().1;
+        assert_eq!((star(1), star(2)), (15, 12));
+        // Winning every round, with one of each shape.
+        assert_eq!(ranked[0], (Cipher::Shapes(vec![2, 1, 0]), 24));
+        assert_eq!(ranked[0].0.describe(&rules), "X=Scissors, Y=Paper, Z=Rock");
+
+        // Five shapes to choose three from.
+        let rules = Rules::from_toml(RPSLS).unwrap();
+        assert_eq!(rank_ciphers(&rules, EXAMPLE).unwrap().len(), 60 + 6);
+    }
+
+    #[test]
+    fn bad_rules() {
+        let err = |toml: &str| Rules::from_toml(toml).unwrap_err().to_string();
+        let outcomes = "[outcomes]\nlose = { letter = \"X\", score = 0 }\ndraw = { letter = \"Y\", score = 3 }\nwin = { letter = \"Z\", score = 6 }\n";
+        let shapes = |beats: &str| format!(
+            "shapes = [{{ name = \"Rock\", opponent = \"A\", player = \"X\", score = 1, beats = [{beats}] }},\n\
+             {{ name = \"Paper\", opponent = \"B\", player = \"Y\", score = 2, beats = [\"Rock\"] }}]\n{outcomes}"
+        );
+        assert!(Rules::from_toml(&shapes("")).is_ok());
+        assert_eq!(err(&shapes("\"Rock\"")), "Rock can't beat itself");
+        assert_eq!(err(&shapes("\"Paper\"")), "Rock and Paper can't both beat each other");
+        assert_eq!(err(&shapes("\"Lizard\"")), "Rock beats Lizard, which isn't a shape");
+        assert_eq!(err(&shapes("").replace("\"B\"", "\"A\"")), "Rock and Paper go by the same letter");
+        assert_eq!(err(&shapes("").replace("\"Y\", score = 3", "\"X\", score = 3")), "Each outcome needs a letter of its own");
+    }
+}
@@ -0,0 +1,318 @@
+use crate::{ File, params };
+use crate::rng::Rng;
+use crate::solution::Extra;
+use itertools::Itertools;
+use std::fmt;
+
+pub const EXTRAS: &[Extra] = &[
+    Extra { name: "shared", description: "The item shared by each rucksack's compartments and each group (--group N, default 3).", run: shared_report },
+    Extra { name: "plan", description: "The fewest moves to get each item type into just one compartment.", run: plan_report },
+];
+
+pub fn star1(file: File) -> Result<u32, anyhow::Error> {
+    check_items(&file.contents)?;
+    let score = file.contents.lines().filter_map(|l| compartments(l).shared().priority()).sum();
+    Ok(score)
+}
+
+pub fn star2(file: File) -> Result<u32, anyhow::Error> {
+    check_items(&file.contents)?;
+    let size = group_size()?;
+    let score = groups(&file.contents, size)
+        .iter()
+        .filter(|group| group.len() == size)
+        .filter_map(|group| badge(group).priority())
+        .sum();
+    Ok(score)
+}
+
+/// A set of items, as a bit for each, with an item's bit being its priority less one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Items(u64);
+
+impl Items {
+    /// Every item there is.
+    const ALL: Items = Items((1 << 52) - 1);
+
+    pub fn new(items: &str) -> Items {
+        Items(items.chars().filter_map(priority).fold(0, |mask, p| mask | 1 << (p - 1)))
+    }
+
+    pub fn and(self, other: Items) -> Items {
+        Items(self.0 & other.0)
+    }
+
+    pub fn len(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// The priority of the lowest priority item here, if there are any.
+    pub fn priority(self) -> Option<u32> {
+        (!self.is_empty()).then(|| self.0.trailing_zeros() + 1)
+    }
+
+    /// Each item, lowest priority first.
+    pub fn iter(self) -> impl Iterator<Item = char> {
+        (1..=52).filter(move |p| self.0 & 1 << (p - 1) != 0).map(item)
+    }
+}
+
+impl fmt::Display for Items {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.iter().try_for_each(|c| write!(f, "{c}"))
+    }
+}
+
+/// a to z are 1 to 26, and A to Z are 27 to 52.
+fn priority(c: char) -> Option<u32> {
+    match c {
+        'a'..='z' => Some(c as u32 - 'a' as u32 + 1),
+        'A'..='Z' => Some(c as u32 - 'A' as u32 + 27),
+        _ => None,
+    }
+}
+
+fn item(priority: u32) -> char {
+    let base = if priority <= 26 { b'a' as u32 + priority - 1 } else { b'A' as u32 + priority - 27 };
+    char::from_u32(base).unwrap_or('?')
+}
+
+/// A rucksack's two compartments, split down the middle.
+struct Compartments {
+    first: Items,
+    second: Items,
+}
+
+impl Compartments {
+    fn shared(&self) -> Items {
+        self.first.and(self.second)
+    }
+}
+
+fn compartments(line: &str) -> Compartments {
+    let (first, second) = line.split_at(line.len() / 2);
+    Compartments { first: Items::new(first), second: Items::new(second) }
+}
+
+/// The item carried by everyone in a group (hopefully just the one).
+fn badge(group: &[&str]) -> Items {
+    group.iter().fold(Items::ALL, |shared, l| shared.and(Items::new(l)))
+}
+
+/// How many elves there are to a group.
+fn group_size() -> Result<usize, anyhow::Error> {
+    let size = params::get("2022.day03.group", 3)?;
+    if size == 0 {
+        anyhow::bail!("Groups need at least one elf in them");
+    }
+    Ok(size)
+}
+
+/// The rucksacks in groups of `size`, the last of which may be short.
+fn groups(input: &str, size: usize) -> Vec<Vec<&str>> {
+    input.lines().chunks(size).into_iter().map(|c| c.collect()).collect()
+}
+
+/// What's wrong with a set of items that should be just the one shared item.
+fn problem(shared: Items) -> Option<String> {
+    match shared.len() {
+        0 => Some("nothing shared".to_owned()),
+        1 => None,
+        _ => Some(format!("{} items shared", shared.len())),
+    }
+}
+
+fn describe(shared: Items) -> String {
+    if shared.is_empty() {
+        return "nothing".to_owned()
+    }
+    shared.iter().map(|c| format!("{c} ({})", priority(c).unwrap_or(0))).join(", ")
+}
+
+fn shared_report(file: File) -> Result<String, anyhow::Error> {
+    check_items(&file.contents)?;
+    let size = group_size()?;
+    let mut out = vec!["Rucksacks:".to_owned()];
+    let mut flagged = 0;
+    for (n, line) in file.contents.lines().enumerate() {
+        let shared = compartments(line).shared();
+        let mut problems: Vec<String> = problem(shared).into_iter().collect();
+        if line.len() % 2 == 1 {
+            problems.push(format!("odd length ({}), so the compartments aren't the same size", line.len()));
+        }
+        flagged += problems.len();
+        let flags = if problems.is_empty() { String::new() } else { format!("  <- {}", problems.join("; ")) };
+        out.push(format!("  Line {}: {}{flags}", n + 1, describe(shared)));
+    }
+
+    out.push(String::new());
+    out.push(format!("Groups of {size}:"));
+    for (n, group) in groups(&file.contents, size).iter().enumerate() {
+        let shared = badge(group);
+        let mut problems: Vec<String> = problem(shared).into_iter().collect();
+        if group.len() < size {
+            problems.push(format!("only {} of {size} elves", group.len()));
+        }
+        flagged += problems.len();
+        let flags = if problems.is_empty() { String::new() } else { format!("  <- {}", problems.join("; ")) };
+        let lines = (n * size + 1, n * size + group.len());
+        out.push(format!("  Group {} (lines {}-{}): {}{flags}", n + 1, lines.0, lines.1, describe(shared)));
+    }
+
+    out.push(String::new());
+    out.push(format!("{flagged} problem(s) found"));
+    Ok(out.join("\n"))
+}
+
+/// A rucksack repacked so that no type of item is in both compartments.
+#[derive(Debug, PartialEq)]
+struct Plan {
+    first: String,
+    second: String,
+    /// Each item moved, how many of it, and whether it went to the second compartment.
+    moved: Vec<(char, usize, bool)>,
+}
+
+impl Plan {
+    fn moves(&self) -> usize {
+        self.moved.iter().map(|(_, n, _)| n).sum()
+    }
+}
+
+/// Repack a rucksack with as few moves as possible. Each item type in both
+/// compartments is dealt with on its own: all of it has to end up on one side,
+/// so the fewest moves is to move whichever side has less of it. When it's
+/// even, the items go to the emptier compartment, to keep them about the same size.
+fn plan(line: &str) -> Plan {
+    let (first, second) = line.split_at(line.len() / 2);
+    let (mut first, mut second) = (first.to_owned(), second.to_owned());
+    let mut moved = Vec::new();
+    for c in compartments(line).shared().iter() {
+        let count = |s: &str| s.chars().filter(|&x| x == c).count();
+        let (a, b) = (count(&first), count(&second));
+        let to_second = a < b || (a == b && first.len() >= second.len());
+        let (from, to) = if to_second { (&mut first, &mut second) } else { (&mut second, &mut first) };
+        from.retain(|x| x != c);
+        to.extend(std::iter::repeat_n(c, a.min(b)));
+        moved.push((c, a.min(b), to_second));
+    }
+    Plan { first, second, moved }
+}
+
+fn plan_report(file: File) -> Result<String, anyhow::Error> {
+    check_items(&file.contents)?;
+    let plans: Vec<Plan> = file.contents.lines().map(plan).collect();
+    let mut out: Vec<String> = plans.iter().enumerate().map(|(n, plan)| {
+        let moved = plan.moved.iter()
+            .map(|(c, count, to_second)| format!("{count} {c} to the {}", if *to_second { "second" } else { "first" }))
+            .join(", ");
+        let moves = if plan.moves() == 1 { "move" } else { "moves" };
+        let moved = if moved.is_empty() { String::new() } else { format!(": {moved}") };
+        format!("Line {}: {} | {} ({} {moves}{moved})", n + 1, plan.first, plan.second, plan.moves())
+    }).collect();
+    out.push(String::new());
+    out.push(format!("Total moves: {}", plans.iter().map(Plan::moves).sum::<usize>()));
+    Ok(out.join("\n"))
+}
+
+/// Items are letters, and nothing else.
+fn check_items(input: &str) -> Result<(), anyhow::Error> {
+    for (n, l) in input.lines().enumerate() {
+        if let Some(c) = l.chars().find(|c| !c.is_ascii_alphabetic()) {
+            anyhow::bail!("Line {}: '{c}' isn't an item", n + 1);
+        }
+    }
+    Ok(())
+}
+
+/// `size` groups of three elves, each with one item in both compartments, and
+/// one item (the badge) that all of the group carry.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..size.max(1) {
+        let mut items: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+        rng.shuffle(&mut items);
+        let badge = items[51];
+        // Elves don't share anything else, and nothing's in both compartments but
+        // the one item, so each elf gets their own items for each compartment.
+        for elf in items[..51].chunks(17) {
+            let (shared, rest) = elf.split_first().unwrap();
+            let (first, second) = rest.split_at(8);
+            let len = rng.below(12) + 4;
+
+            let mut a = vec![*shared, badge];
+            a.extend((2..len).map(|_| *rng.pick(first)));
+            let mut b = vec![*shared];
+            b.extend((1..len).map(|_| *rng.pick(second)));
+            rng.shuffle(&mut a);
+            rng.shuffle(&mut b);
+            out.extend(a.into_iter().chain(b));
+            out.push('\n');
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg\n\
+        wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\nttgJtRgJQctTZtZT\nCrZsJsPPZsGzwwsLwLmpwMDw\n";
+
+    #[test]
+    fn example() {
+        assert_eq!(star1(File { contents: EXAMPLE.to_string() }).unwrap(), 157);
+        assert_eq!(star2(File { contents: EXAMPLE.to_string() }).unwrap(), 70);
+    }
+
+    #[test]
+    fn items() {
+        let items = Items::new("aZzA");
+        assert_eq!((items.len(), items.priority()), (4, Some(1)));
+        assert_eq!(items.to_string(), "azAZ");
+        assert_eq!(Items::new("L").priority(), Some(38));
+        assert_eq!(Items::ALL.iter().collect::<String>().len(), 52);
+        assert!(Items::new("ab").and(Items::new("cd")).is_empty());
+    }
+
+    #[test]
+    fn plans() {
+        let p = plan("vJrwpWtwJgWrhcsFMMfFFhFp");
+        assert_eq!((p.first.as_str(), p.second.as_str(), p.moves()), ("vJrwWtwJgWr", "hcsFMMfFFhFpp", 1));
+
+        // The a in the second half and the b in the first are outnumbered.
+        let p = plan("aabbab");
+        assert_eq!((p.first.as_str(), p.second.as_str()), ("aaa", "bbb"));
+        assert_eq!(p.moved, [('a', 1, false), ('b', 1, true)]);
+
+        // The a's and x's are even, so each goes to what's then the emptier side.
+        let p = plan("abxxaxyx");
+        assert_eq!((p.first.as_str(), p.second.as_str(), p.moves()), ("bxxxx", "aya", 3));
+
+        assert!(Items::new(&p.first).and(Items::new(&p.second)).is_empty());
+        assert_eq!(plan("abcd"), Plan { first: "ab".to_owned(), second: "cd".to_owned(), moved: vec![] });
+    }
+
+    #[test]
+    fn groups_of_any_size() {
+        // In twos, the first pair share five items.
+        params::with(&[("2022.day03.group", "2")], || {
+            let groups: Vec<Items> = groups(EXAMPLE, 2).iter().map(|g| badge(g)).collect();
+            assert_eq!(groups.len(), 3);
+            assert_eq!(groups[0].to_string(), "frsFM");
+            // Groups with more than one shared item count the lowest priority one.
+            let sum: u32 = groups.iter().filter_map(|g| g.priority()).sum();
+            assert_eq!(star2(File { contents: EXAMPLE.to_string() }).unwrap(), sum);
+        });
+
+        let report = params::with(&[("2022.day03.group", "4")], || shared_report(File { contents: format!("{EXAMPLE}abc\n") })).unwrap();
+        assert!(report.contains("Line 1: p (16)\n"));
+        assert!(report.contains("Line 7: nothing  <- nothing shared; odd length (3)"), "{report}");
+        assert!(report.contains("Group 2 (lines 5-7): nothing  <- nothing shared; only 3 of 4 elves"), "{report}");
+    }
+}
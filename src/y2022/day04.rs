@@ -0,0 +1,296 @@
+use crate::File;
+use crate::rng::Rng;
+use crate::solution::Extra;
+use std::fmt;
+
+pub const EXTRAS: &[Extra] = &[
+    Extra { name: "overlaps", description: "Which elves' assignments overlap on each line, and by how much.", run: overlap_report },
+    Extra { name: "coverage", description: "How many elves are on each section, over every assignment on every line.", run: coverage_report },
+];
+
+pub fn parse(input: &str) -> Result<(), anyhow::Error> {
+    parse_input(input)?;
+    Ok(())
+}
+
+/// The assignments on each line, however many elves are on it.
+fn parse_input(input: &str) -> Result<Vec<Vec<Sections>>, anyhow::Error> {
+    input.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()).map(|(n, l)| {
+        l.split(',')
+            .map(|range| range.parse().map_err(|e| anyhow::anyhow!("Line {}: {e}", n + 1)))
+            .collect()
+    }).collect()
+}
+
+pub fn star1(file: File) -> Result<usize, anyhow::Error> {
+    Ok(parse_input(&file.contents)?.iter().filter(|group| any_contains(group)).count())
+}
+
+pub fn star2(file: File) -> Result<usize, anyhow::Error> {
+    Ok(parse_input(&file.contents)?.iter().filter(|group| !all_disjoint(group)).count())
+}
+
+/// The highest numbered section there can be. Keeping well below the most a
+/// usize can hold means that the length of any range of sections, and the
+/// section just after one, can always be worked out without overflowing.
+pub const MAX_SECTION: usize = u32::MAX as usize;
+
+/// The sections from `start` to `end`, inclusive, up to `MAX_SECTION`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sections {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Sections {
+    pub fn len(self) -> usize {
+        self.end - self.start + 1
+    }
+
+    /// Whether all of `other` is within these sections.
+    pub fn contains(self, other: Sections) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    /// The sections in both, if there are any.
+    pub fn overlap(self, other: Sections) -> Option<Sections> {
+        let (start, end) = (self.start.max(other.start), self.end.min(other.end));
+        (start <= end).then_some(Sections { start, end })
+    }
+}
+
+impl std::str::FromStr for Sections {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let range = || {
+            let (start, end) = s.trim().split_once('-')?;
+            Some(Sections { start: start.parse().ok()?, end: end.parse().ok()? }).filter(|r| r.start <= r.end)
+        };
+        let range = range().ok_or_else(|| anyhow::anyhow!("'{}' isn't a range of sections like '2-4'", s.trim()))?;
+        if range.end > MAX_SECTION {
+            anyhow::bail!("'{}' goes past the last section there can be ({MAX_SECTION})", s.trim());
+        }
+        Ok(range)
+    }
+}
+
+impl fmt::Display for Sections {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+/// Each pair of elves in a group, by their place in it.
+fn pairs(group: &[Sections]) -> impl Iterator<Item = (usize, usize)> + '_ {
+    (0..group.len()).flat_map(move |a| (a + 1..group.len()).map(move |b| (a, b)))
+}
+
+/// Whether any elf's assignment is entirely within another's.
+pub fn any_contains(group: &[Sections]) -> bool {
+    pairs(group).any(|(a, b)| group[a].contains(group[b]) || group[b].contains(group[a]))
+}
+
+/// Whether any two elves' assignments overlap at all.
+pub fn any_overlap(group: &[Sections]) -> bool {
+    pairs(group).any(|(a, b)| group[a].overlap(group[b]).is_some())
+}
+
+/// Whether no two elves share a section.
+pub fn all_disjoint(group: &[Sections]) -> bool {
+    !any_overlap(group)
+}
+
+/// Every pair of elves (numbered from 1 in the group) whose assignments overlap,
+/// and the sections they both have.
+fn overlaps(group: &[Sections]) -> Vec<(usize, usize, Sections)> {
+    pairs(group)
+        .filter_map(|(a, b)| Some((a + 1, b + 1, group[a].overlap(group[b])?)))
+        .collect()
+}
+
+fn overlap_report(file: File) -> Result<String, anyhow::Error> {
+    let groups = parse_input(&file.contents)?;
+    let mut out = Vec::new();
+    for (n, group) in groups.iter().enumerate() {
+        let assignments: Vec<String> = group.iter().map(|s| s.to_string()).collect();
+        let overlaps = overlaps(group);
+        if overlaps.is_empty() {
+            out.push(format!("Group {} ({}): no overlaps", n + 1, assignments.join(",")));
+            continue
+        }
+        out.push(format!("Group {} ({}):", n + 1, assignments.join(",")));
+        for (a, b, both) in overlaps {
+            let sections = if both.len() == 1 { "section" } else { "sections" };
+            let contained = if both == group[a - 1] || both == group[b - 1] { ", one within the other" } else { "" };
+            out.push(format!("  elves {a} and {b} overlap by {} {sections} ({both}){contained}", both.len()));
+        }
+    }
+
+    let count = |f: fn(&[Sections]) -> bool| groups.iter().filter(|group| f(group)).count();
+    out.push(String::new());
+    out.push(format!(
+        "{} groups: {} with an assignment within another, {} with overlaps, {} disjoint",
+        groups.len(), count(any_contains), count(any_overlap), count(all_disjoint)
+    ));
+    Ok(out.join("\n"))
+}
+
+/// Sweep across every assignment, from the first section anyone has to the
+/// last, giving each run of sections with the same number of elves on them
+/// (including runs of nobody), in order.
+fn coverage(assignments: impl IntoIterator<Item = Sections>) -> Vec<(Sections, usize)> {
+    // An elf joins at the start of their sections, and leaves just after the end
+    // (if there's any section after it).
+    let mut events: Vec<(usize, isize)> = assignments
+        .into_iter()
+        .flat_map(|s| [Some((s.start, 1)), s.end.checked_add(1).map(|after| (after, -1))])
+        .flatten()
+        .collect();
+    events.sort_unstable();
+
+    let mut runs: Vec<(Sections, usize)> = Vec::new();
+    let mut elves: isize = 0;
+    let mut n = 0;
+    while n < events.len() {
+        let at = events[n].0;
+        while n < events.len() && events[n].0 == at {
+            elves += events[n].1;
+            n += 1;
+        }
+        let end = match events.get(n) {
+            Some(&(next, _)) => next - 1,
+            // Anyone still on after the last event has every section from here on.
+            None if elves > 0 => usize::MAX,
+            // Otherwise nobody's on anything after it, so that's where the sweep stops.
+            None => break,
+        };
+        match runs.last_mut() {
+            // One elf leaving just as another joins doesn't start a new run.
+            Some((run, count)) if *count == elves as usize => run.end = end,
+            _ => runs.push((Sections { start: at, end }, elves as usize)),
+        }
+    }
+    runs
+}
+
+fn coverage_report(file: File) -> Result<String, anyhow::Error> {
+    let runs = coverage(parse_input(&file.contents)?.into_iter().flatten());
+    let (Some(first), Some(last)) = (runs.first(), runs.last()) else {
+        anyhow::bail!("There are no assignments to look at");
+    };
+    let span = Sections { start: first.0.start, end: last.0.end };
+    let list = |runs: Vec<Sections>| runs.iter().map(|s| if s.len() == 1 { s.start.to_string() } else { s.to_string() }).collect::<Vec<_>>().join(", ");
+
+    let most = runs.iter().map(|(_, elves)| *elves).max().unwrap_or(0);
+    let peaks: Vec<Sections> = runs.iter().filter(|(_, elves)| *elves == most).map(|(s, _)| *s).collect();
+    let gaps: Vec<Sections> = runs.iter().filter(|(_, elves)| *elves == 0).map(|(s, _)| *s).collect();
+    let uncovered: usize = gaps.iter().map(|s| s.len()).sum();
+    let covered = span.len() - uncovered;
+
+    let mut out = vec![format!("Most elves on one section: {most}, on {}", list(peaks))];
+    if gaps.is_empty() {
+        out.push(format!("Every section from {span} has someone on it"));
+    } else {
+        out.push(format!("Sections from {span} that nobody has: {uncovered} ({})", list(gaps)));
+    }
+    out.push(format!("Sections covered: {covered}"));
+    Ok(out.join("\n"))
+}
+
+/// `size` pairs of section assignments.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut range = || {
+        let start = rng.range(1, 99);
+        (start, rng.range(start, 99))
+    };
+    (0..size.max(1)).map(|_| {
+        let (a, b) = (range(), range());
+        format!("{}-{},{}-{}\n", a.0, a.1, b.0, b.1)
+    }).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8\n";
+
+    #[test]
+    fn example() {
+        assert_eq!(star1(File { contents: EXAMPLE.to_string() }).unwrap(), 2);
+        assert_eq!(star2(File { contents: EXAMPLE.to_string() }).unwrap(), 4);
+    }
+
+    #[test]
+    fn ranges() {
+        let r = |s: &str| s.parse::<Sections>().unwrap();
+        assert_eq!(r("2-8").overlap(r("3-9")), Some(r("3-8")));
+        assert_eq!(r("2-4").overlap(r("5-9")), None);
+        // Overlapping only at the end of the other range counts.
+        assert_eq!(r("1-5").overlap(r("5-5")), Some(r("5-5")));
+        assert!(r("1-5").contains(r("5-5")) && !r("5-5").contains(r("1-5")));
+        assert_eq!("5-3".parse::<Sections>().unwrap_err().to_string(), "'5-3' isn't a range of sections like '2-4'");
+    }
+
+    #[test]
+    fn biggest_ranges() {
+        let all = Sections { start: 0, end: MAX_SECTION };
+        assert_eq!(format!("0-{MAX_SECTION}").parse::<Sections>().unwrap(), all);
+        assert_eq!(all.len(), MAX_SECTION + 1);
+        let err = format!("0-{}", MAX_SECTION + 1).parse::<Sections>().unwrap_err();
+        assert_eq!(err.to_string(), format!("'0-{}' goes past the last section there can be ({MAX_SECTION})", MAX_SECTION + 1));
+        assert!(format!("0-{}", usize::MAX).parse::<Sections>().is_err());
+
+        let report = overlap_report(File { contents: format!("0-{MAX_SECTION},{MAX_SECTION}-{MAX_SECTION}\n") }).unwrap();
+        assert!(report.contains(&format!("overlap by 1 section ({MAX_SECTION}-{MAX_SECTION}), one within the other")), "{report}");
+        assert_eq!(star1(File { contents: format!("0-{MAX_SECTION},0-{MAX_SECTION}\n") }).unwrap(), 1);
+    }
+
+    #[test]
+    fn groups_of_any_size() {
+        let groups = parse_input("1-3,5-7,2-6\n4-4\n1-2,3-4,5-6\n").unwrap();
+        assert_eq!(overlaps(&groups[0]), [(1, 3, "2-3".parse().unwrap()), (2, 3, "5-6".parse().unwrap())]);
+        assert!(any_overlap(&groups[0]) && !any_contains(&groups[0]));
+        // A lone elf can't overlap with anyone.
+        assert!(all_disjoint(&groups[1]) && !any_contains(&groups[1]));
+        assert!(all_disjoint(&groups[2]));
+
+        let err = parse_input("1-3,5-7\n2-x\n").unwrap_err();
+        assert_eq!(err.to_string(), "Line 2: '2-x' isn't a range of sections like '2-4'");
+    }
+
+    #[test]
+    fn sweep() {
+        let runs = coverage(parse_input(EXAMPLE).unwrap().into_iter().flatten());
+        let elves: Vec<usize> = runs.iter().map(|(_, elves)| *elves).collect();
+        assert_eq!(runs.first().unwrap().0.start, 2);
+        assert_eq!(runs.last().unwrap().0.end, 9);
+        // Sections 2 to 9, with 4 and 5 both having 7 elves on them.
+        assert_eq!(elves, [4, 5, 7, 8, 6, 4, 1]);
+        assert_eq!(runs[2].0, "4-5".parse().unwrap());
+
+        let report = coverage_report(File { contents: "1-2,5-6\n5-5,9-9\n".to_string() }).unwrap();
+        assert_eq!(report, "Most elves on one section: 2, on 5\n\
+            Sections from 1-9 that nobody has: 4 (3-4, 7-8)\n\
+            Sections covered: 5");
+        assert!(coverage_report(File { contents: String::new() }).is_err());
+    }
+
+    #[test]
+    fn sweep_to_the_end() {
+        let everything = Sections { start: 0, end: usize::MAX };
+        let runs = coverage([everything, Sections { start: 1, end: 2 }]);
+        assert_eq!(runs, [
+            (Sections { start: 0, end: 0 }, 1),
+            (Sections { start: 1, end: 2 }, 2),
+            (Sections { start: 3, end: usize::MAX }, 1),
+        ]);
+
+        let report = coverage_report(File { contents: format!("0-{MAX_SECTION},1-2\n") }).unwrap();
+        assert_eq!(report, format!("Most elves on one section: 2, on 1-2\n\
+            Every section from 0-{MAX_SECTION} has someone on it\n\
+            Sections covered: {}", MAX_SECTION + 1));
+        // Past the last section, it's an error rather than an overflow.
+        assert!(coverage_report(File { contents: format!("0-{},1-2\n", usize::MAX) }).is_err());
+    }
+}
@@ -0,0 +1,153 @@
+use crate::File;
+use crate::rng::Rng;
+
+#[derive(Debug)]
+struct Command {
+    count: usize,
+    // Which stacks, counting from 0 (unlike in the input).
+    from: usize,
+    to: usize
+}
+
+pub fn parse(input: &str) -> Result<(), anyhow::Error> {
+    parse_input(input)?;
+    Ok(())
+}
+
+fn parse_input(input: &str) -> Result<(Vec<Vec<char>>, Vec<Command>), anyhow::Error> {
+    let mut split = input.split("\n\n");
+
+    // Turn:
+    //
+    // [D]
+    // [N] [C]
+    // [Z] [M] [P]
+    //  1   2   3
+    //
+    // Into:
+    //
+    // vec![vec![Z,N,D], vec![M,C], vec![P]]
+    let stack_str = split.next().unwrap_or_default();
+    let stack = {
+        let stack_strs: Vec<&[u8]> = stack_str
+            .lines()
+            .filter(|l| l.contains('['))
+            .map(|l| l.as_bytes())
+            .collect();
+
+        let mut stack_chars: Vec<Vec<char>> = Vec::new();
+        let mut idx = 1; // look at specific indexes for letters; 1, 5, 9...
+        'lo: while !stack_strs.is_empty() {
+            let mut chars = Vec::new();
+            // Start from the bottom of the stack in each column
+            for (i,s) in stack_strs.iter().rev().enumerate() {
+                let Some(c) = s.get(idx) else {
+                    if i == 0 {
+                        // if no entry on the bottom, we're done
+                        break 'lo
+                    } else {
+                        // else we are just done with this column.
+                        break;
+                    }
+                };
+                if c.is_ascii_uppercase() {
+                    chars.push(*c as char);
+                }
+            }
+            if !chars.is_empty() {
+                stack_chars.push(chars);
+            }
+            idx += 4;
+        }
+        stack_chars
+    };
+
+    // And now, the commands (numbering lines from after the stacks and blank line).
+    let first_line = stack_str.lines().count() + 2;
+    let cmd_regex = regex!("^move ([0-9]+) from ([0-9]+) to ([0-9]+)$");
+    let cmds = split.next().unwrap_or_default().lines().enumerate().filter(|(_, l)| !l.trim().is_empty()).map(|(n, l)| {
+        let line = first_line + n;
+        let caps = cmd_regex.captures(l.trim())
+            .ok_or_else(|| anyhow::anyhow!("Line {line}: expected 'move N from N to N', got '{l}'"))?;
+        let as_num = |n: usize| caps[n].parse::<usize>()
+            .map_err(|e| anyhow::anyhow!("Line {line}: {e}"));
+        let stack_idx = |n: usize| match as_num(n)? {
+            idx @ 1.. if idx <= stack.len() => Ok(idx - 1),
+            idx => anyhow::bail!("Line {line}: there's no stack {idx}; they're numbered 1 to {}", stack.len()),
+        };
+        Ok(Command { count: as_num(1)?, from: stack_idx(2)?, to: stack_idx(3)? })
+    });
+
+    let cmds = cmds.collect::<Result<_, anyhow::Error>>()?;
+    Ok((stack, cmds))
+}
+
+pub fn star1(file: File) -> Result<String, anyhow::Error> {
+    let (mut stack, commands) = parse_input(&file.contents)?;
+
+    for Command { count, from, to } in commands {
+        for _ in 0..count.min(stack[from].len()) {
+            if let Some(item) = stack[from].pop() {
+                stack[to].push(item);
+            }
+        }
+    }
+
+    let s: String = stack.iter().filter_map(|items| items.last().copied()).collect();
+    Ok(s)
+}
+
+pub fn star2(file: File) -> Result<String, anyhow::Error> {
+    let (mut stack, commands) = parse_input(&file.contents)?;
+
+    for Command { count, from, to } in commands {
+        let mut carried = Vec::new();
+        for _ in 0..count.min(stack[from].len()) {
+            if let Some(item) = stack[from].pop() {
+                carried.push(item);
+            }
+        }
+        while let Some(item) = carried.pop() {
+            stack[to].push(item);
+        }
+    }
+
+    let s: String = stack.iter().filter_map(|items| items.last().copied()).collect();
+    Ok(s)
+}
+
+/// Some stacks of crates and then `size` moves between them.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut stacks: Vec<Vec<char>> = (0..rng.range(3, 9))
+        .map(|_| (0..rng.range(1, 8)).map(|_| (b'A' + rng.below(26) as u8) as char).collect())
+        .collect();
+
+    let mut out = String::new();
+    let height = stacks.iter().map(|s| s.len()).max().unwrap_or(0);
+    for row in (0..height).rev() {
+        let line: Vec<String> = stacks
+            .iter()
+            .map(|s| s.get(row).map_or("   ".to_owned(), |c| format!("[{c}]")))
+            .collect();
+        out.push_str(&line.join(" "));
+        out.push('\n');
+    }
+    let numbers: Vec<String> = (1..=stacks.len()).map(|n| format!(" {n} ")).collect();
+    out.push_str(&numbers.join(" "));
+    out.push_str("\n\n");
+
+    // Keep track of the stacks so that we only ever move crates that are there.
+    for _ in 0..size {
+        let from = rng.below(stacks.len());
+        if stacks[from].is_empty() {
+            continue
+        }
+        let to = (from + 1 + rng.below(stacks.len() - 1)) % stacks.len();
+        let height = stacks[from].len();
+        let count = rng.range(1, height as i64) as usize;
+        let moved = stacks[from].split_off(height - count);
+        stacks[to].extend(moved);
+        out.push_str(&format!("move {count} from {} to {}\n", from + 1, to + 1));
+    }
+    out
+}
@@ -0,0 +1,34 @@
+use crate::File;
+use crate::rng::Rng;
+use std::collections::HashSet;
+
+pub fn star1(file: File) -> Result<usize, anyhow::Error> {
+    distinct_chars_at(file.contents.as_bytes(), 4)
+        .ok_or_else(|| anyhow::anyhow!("Didn't find 4 different chars"))
+}
+
+pub fn star2(file: File) -> Result<usize, anyhow::Error> {
+    distinct_chars_at(file.contents.as_bytes(), 14)
+        .ok_or_else(|| anyhow::anyhow!("Didn't find 14 different chars"))
+}
+
+fn distinct_chars_at(input: &[u8], len: usize) -> Option<usize> {
+    for (chars,idx) in input.windows(len).zip(len..) {
+        if HashSet::<&u8>::from_iter(chars).len() == len {
+            return Some(idx)
+        }
+    }
+    None
+}
+
+/// About `size` characters before the first start-of-message marker.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    // Three letters can't make a marker, so nothing turns up until the end.
+    let mut out: String = (0..size).map(|_| *rng.pick(&['a', 'b', 'c'])).collect();
+    let mut marker: Vec<char> = ('a'..='z').collect();
+    rng.shuffle(&mut marker);
+    out.extend(&marker[..14]);
+    out.extend((0..size).map(|_| (b'a' + rng.below(26) as u8) as char));
+    out.push('\n');
+    out
+}
@@ -1,10 +1,11 @@
-use super::File;
+use crate::{ File, params };
+use crate::rng::Rng;
 use std::collections::HashMap;
 use yap::{ IntoTokens, Tokens, TokenLocation };
 
 pub fn star1(file: File) -> anyhow::Result<usize> {
     let commands = parse_input(&file.contents)?;
-    let dir = Directory::from_commands(commands);
+    let dir = Directory::from_commands(commands)?;
 
     // Add up all directories with size <= 100_000
     let sum: usize = dir
@@ -23,13 +24,13 @@ pub fn star1(file: File) -> anyhow::Result<usize> {
 
 pub fn star2(file: File) -> Result<usize, anyhow::Error> {
     let commands = parse_input(&file.contents)?;
-    let dir = Directory::from_commands(commands);
+    let dir = Directory::from_commands(commands)?;
 
     let need_to_reclaim = {
         let used_space = dir.size();
-        let total_space = 70000000;
-        let required_space = 30000000;
-        required_space - (total_space - used_space)
+        let total_space: usize = params::get("2022.day07.disk_size", 70000000)?;
+        let required_space: usize = params::get("2022.day07.needed_space", 30000000)?;
+        required_space - total_space.saturating_sub(used_space).min(required_space)
     };
 
     // Find smallest directory to delete to free up `need_to_reclaim`.
@@ -53,10 +54,10 @@ enum Item {
 }
 
 impl Item {
-    fn unwrap_directory_mut(&mut self) -> &mut Directory {
+    fn as_directory_mut(&mut self) -> Option<&mut Directory> {
         match self {
-            Item::Directory(dir) => dir,
-            _ => panic!("Not a directory")
+            Item::Directory(dir) => Some(dir),
+            _ => None
         }
     }
 }
@@ -75,9 +76,7 @@ impl Directory {
 
         std::iter::from_fn(move || {
             loop {
-                let Some(mut it) = stack.pop() else {
-                    return None
-                };
+                let mut it = stack.pop()?;
                 match it.next() {
                     Some((name, dir @ Item::Directory(inner))) => {
                         stack.push(it);
@@ -114,7 +113,7 @@ impl Directory {
         }
         Some(cwd)
     }
-    fn from_commands(commands: Vec<Command>) -> Directory {
+    fn from_commands(commands: Vec<Command>) -> anyhow::Result<Directory> {
         let mut root = Directory::new();
         let mut cwd = &mut root;
         let mut path = vec![];
@@ -127,14 +126,16 @@ impl Directory {
                 },
                 Command::Cd(CdPath::UpOne) => {
                     path.pop();
-                    cwd = root.cd_to_mut(&path).unwrap();
+                    cwd = root.cd_to_mut(&path)
+                        .ok_or_else(|| anyhow::anyhow!("Can't cd back up to /{}", path.join("/")))?;
                 }
                 Command::Cd(CdPath::Into(dir)) => {
                     path.push(dir.clone());
                     cwd = cwd.0
                         .entry(dir)
                         .or_insert(Item::Directory(Directory::new()))
-                        .unwrap_directory_mut();
+                        .as_directory_mut()
+                        .ok_or_else(|| anyhow::anyhow!("Can't cd into /{}; it's a file", path.join("/")))?;
                 },
                 Command::Ls(output) => {
                     for item in output {
@@ -150,10 +151,15 @@ impl Directory {
                 }
             }
         }
-        root
+        Ok(root)
     }
 }
 
+pub fn parse(input: &str) -> Result<(), anyhow::Error> {
+    parse_input(input)?;
+    Ok(())
+}
+
 fn parse_input(input: &str) -> anyhow::Result<Vec<Command>> {
     let mut tokens = input.into_tokens();
     tokens.many(|toks| {
@@ -204,9 +210,11 @@ fn parse_ls_output(input: &mut impl Tokens<Item=char>) -> anyhow::Result<Vec<LsO
             let dir_name = input.tokens_while(|c| *c != '\n').collect();
             out.push(LsOutput::Dir { name: dir_name })
         } else {
-            let size_str: String = input.tokens_while(|c| c.is_numeric()).collect();
+            let size_str: String = input.tokens_while(|c| c.is_ascii_digit()).collect();
             if size_str.is_empty() { break }
-            let size: usize = size_str.parse().unwrap();
+            // No bigger than a u32, so that adding up the sizes can't overflow.
+            let size: u32 = size_str.parse().map_err(|e| anyhow::anyhow!("Bad file size '{size_str}': {e}"))?;
+            let size = size as usize;
             input.token(' ');
             let name = input.tokens_while(|c| *c != '\n').collect();
             out.push(LsOutput::File { size, name })
@@ -233,4 +241,52 @@ enum CdPath {
 enum LsOutput {
     File { size: usize, name: String },
     Dir { name: String }
-}
\ No newline at end of file
+}
+
+/// A terminal session exploring a file system with `size` directories.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    // Make up a tree; each directory's parent is one of the ones before it.
+    let dirs = size.max(2);
+    let parents: Vec<usize> = (1..dirs).map(|n| rng.below(n)).collect();
+    let mut children = vec![Vec::new(); dirs];
+    for (idx, parent) in parents.iter().enumerate() {
+        children[*parent].push(idx + 1);
+    }
+
+    fn name(rng: &mut Rng, n: usize) -> String {
+        let base: String = (0..rng.range(1, 8)).map(|_| (b'a' + rng.below(26) as u8) as char).collect();
+        format!("{base}{n}")
+    }
+    let names: Vec<String> = (0..dirs).map(|n| name(rng, n)).collect();
+
+    // Keep the files small enough that they add up to less than 40_000_000 (so
+    // that everything fits on the disk with room to spare) and then add one big
+    // one to the first directory, which will then always be big enough to
+    // delete to free up the space needed.
+    let max_file = (40_000_000 / (dirs as i64 * 4)).clamp(1, 400_000);
+    let big_file = rng.range(1, 30_000_000);
+
+    struct Gen<'a> { children: &'a [Vec<usize>], names: &'a [String], max_file: i64, big_file: i64 }
+    fn visit(rng: &mut Rng, dir: usize, gen: &Gen, out: &mut String) {
+        out.push_str("$ ls\n");
+        for child in &gen.children[dir] {
+            out.push_str(&format!("dir {}\n", gen.names[*child]));
+        }
+        for n in 0..rng.below(5) {
+            let ext = if rng.chance(0.5) { ".txt" } else { "" };
+            out.push_str(&format!("{} {}{ext}\n", rng.range(1, gen.max_file), name(rng, n)));
+        }
+        if dir == 1 {
+            out.push_str(&format!("{} big.dat\n", gen.big_file));
+        }
+        for child in &gen.children[dir] {
+            out.push_str(&format!("$ cd {}\n", gen.names[*child]));
+            visit(rng, *child, gen, out);
+            out.push_str("$ cd ..\n");
+        }
+    }
+
+    let mut out = String::from("$ cd /\n");
+    visit(rng, 0, &Gen { children: &children, names: &names, max_file, big_file }, &mut out);
+    out
+}
@@ -1,18 +1,26 @@
-use super::File;
+use crate::File;
+use crate::rng::Rng;
 use std::collections::HashMap;
 
 type Grid = HashMap<(i32,i32), u32>;
 
-fn parse_input(input: &str) -> Grid {
+pub fn parse(input: &str) -> Result<(), anyhow::Error> {
+    parse_input(input)?;
+    Ok(())
+}
+
+fn parse_input(input: &str) -> Result<Grid, anyhow::Error> {
     input.trim().lines().enumerate().flat_map(|(y,l)| {
         l.chars().enumerate().map(move |(x,h)| {
-            ((x as i32,y as i32), h as u32 - 48)
+            let height = h.to_digit(10)
+                .ok_or_else(|| anyhow::anyhow!("Line {} column {}: '{h}' isn't a tree height", y + 1, x + 1))?;
+            Ok(((x as i32,y as i32), height))
         })
     }).collect()
 }
 
 pub fn star1(file: File) -> Result<usize, anyhow::Error> {
-    let grid = parse_input(&file.contents);
+    let grid = parse_input(&file.contents)?;
     let visible_trees = grid
         .iter()
         .filter(|((x,y), height)| is_visible((*x,*y), **height, &grid))
@@ -21,13 +29,14 @@ pub fn star1(file: File) -> Result<usize, anyhow::Error> {
     Ok(visible_trees)
 }
 
-pub fn star2(file: File) -> Result<u32, anyhow::Error> {
-    let grid = parse_input(&file.contents);
-    let most_scenic = grid
-        .iter()
-        .map(|((x,y), height)| scenic_score((*x,*y), *height, &grid))
-        .max()
-        .unwrap_or(0);
+pub fn star2(file: File) -> Result<u64, anyhow::Error> {
+    let grid = parse_input(&file.contents)?;
+    let mut most_scenic = 0;
+    for ((x,y), height) in &grid {
+        let score = scenic_score((*x,*y), *height, &grid)
+            .ok_or_else(|| anyhow::anyhow!("The scenic score at ({x},{y}) is too big to work out"))?;
+        most_scenic = most_scenic.max(score);
+    }
 
     Ok(most_scenic)
 }
@@ -55,13 +64,13 @@ where F: Fn((i32, i32)) -> (i32,i32)
     }
 }
 
-fn scenic_score(xy: (i32,i32), height: u32, grid: &Grid) -> u32 {
+fn scenic_score(xy: (i32,i32), height: u32, grid: &Grid) -> Option<u64> {
     let left = visible_from_direction(xy, height, grid, |(x,y)| (x - 1, y));
     let right = visible_from_direction(xy, height, grid, |(x,y)| (x + 1, y));
     let up = visible_from_direction(xy, height, grid, |(x,y)| (x, y - 1));
     let down = visible_from_direction(xy, height, grid, |(x,y)| (x, y + 1));
 
-    left * right * up * down
+    [left, right, up, down].into_iter().try_fold(1u64, |score, n| score.checked_mul(n.into()))
 }
 
 fn visible_from_direction<F>(mut xy: (i32,i32), height: u32, grid: &Grid, next: F) -> u32
@@ -80,4 +89,14 @@ where F: Fn((i32, i32)) -> (i32,i32)
             return count;
         }
     }
-}
\ No newline at end of file
+}
+
+/// A `size` by `size` grid of tree heights.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    (0..size).map(|_| {
+        let mut row: String = (0..size).map(|_| (b'0' + rng.below(10) as u8) as char).collect();
+        row.push('\n');
+        row
+    }).collect()
+}
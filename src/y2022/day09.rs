@@ -1,6 +1,11 @@
-use super::File;
+use crate::File;
+use crate::rng::Rng;
+#[cfg(feature = "viz")]
+use crate::utils;
 use yap::{ IntoTokens, Tokens };
 use std::collections::HashSet;
+#[cfg(feature = "viz")]
+use std::collections::HashMap;
 use std::cell::Cell;
 use itertools::Itertools;
 
@@ -8,7 +13,7 @@ pub fn star1(file: File) -> Result<usize, anyhow::Error> {
     let mut seen_tail_pos = HashSet::<(i32,i32)>::from_iter([(0,0)]);
     let mut head_pos = (0,0);
     let mut tail_pos = (0,0);
-    for (dir, count) in parse_input(&file.contents) {
+    for (dir, count) in parse_input(&file.contents)? {
         for _ in 0..count {
             head_pos = dir.nudge(head_pos);
             tail_pos = move_tail(tail_pos, head_pos);
@@ -19,10 +24,25 @@ pub fn star1(file: File) -> Result<usize, anyhow::Error> {
 }
 
 pub fn star2(file: File) -> Result<usize, anyhow::Error> {
+    Ok(long_rope_tail_positions(&file.contents)?.len())
+}
+
+/// Draw every position that the tail of the long rope visited.
+#[cfg(feature = "viz")]
+pub fn visualise(file: File) -> Result<String, anyhow::Error> {
+    let mut cells: HashMap<(i32,i32), char> = long_rope_tail_positions(&file.contents)?
+        .into_iter()
+        .map(|xy| (xy, '#'))
+        .collect();
+    cells.insert((0,0), 's');
+    Ok(utils::render_grid(&cells, '.'))
+}
+
+fn long_rope_tail_positions(input: &str) -> Result<HashSet<(i32,i32)>, anyhow::Error> {
     let mut seen_tail_pos = HashSet::<(i32,i32)>::from_iter([(0,0)]);
     let mut head_pos = (0,0);
     let tails = vec![Cell::new((0,0)); 9];
-    for (dir, count) in parse_input(&file.contents) {
+    for (dir, count) in parse_input(input)? {
         for _ in 0..count {
             head_pos = dir.nudge(head_pos);
             tails[0].set(move_tail(tails[0].get(), head_pos));
@@ -34,10 +54,11 @@ pub fn star2(file: File) -> Result<usize, anyhow::Error> {
             seen_tail_pos.insert(tails.last().unwrap().get());
         }
     }
-    Ok(seen_tail_pos.len())
+    Ok(seen_tail_pos)
 }
 
 // This could be much less verbose, but oh well.
+#[allow(clippy::if_same_then_else)]
 fn move_tail((tx,ty): (i32,i32), (hx,hy): (i32,i32)) -> (i32,i32) {
     // same; leave
     if hx == tx && hy == ty { (tx,ty) }
@@ -76,25 +97,37 @@ fn pull_1d(t: i32, h: i32) -> i32 {
     else { h }
 }
 
-fn parse_input(input: &str) -> impl Iterator<Item=(Direction, i32)> + '_ {
-    input.trim().lines().filter_map(move |l| {
+pub fn parse(input: &str) -> Result<(), anyhow::Error> {
+    parse_input(input)?;
+    Ok(())
+}
+
+fn parse_input(input: &str) -> Result<Vec<(Direction, i32)>, anyhow::Error> {
+    input.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()).map(|(n, l)| {
+        let l = l.trim();
         let mut toks = l.into_tokens();
-        let dir = match toks.next()? {
-            'U' => Direction::Up,
-            'D' => Direction::Down,
-            'L' => Direction::Left,
-            _ => Direction::Right
+        let dir = match toks.next() {
+            Some('U') => Direction::Up,
+            Some('D') => Direction::Down,
+            Some('L') => Direction::Left,
+            Some('R') => Direction::Right,
+            _ => anyhow::bail!("Line {}: expected a move starting with U, D, L or R, got '{l}'", n + 1)
         };
 
         toks.token(' ');
-        let count: i32 = toks
-            .tokens_while(|c| c.is_numeric())
+        // No more than a u16 at a time, so that the rope can't end up anywhere
+        // too far away to keep track of.
+        let count: Option<u16> = toks
+            .tokens_while(|c| c.is_ascii_digit())
             .collect::<String>()
             .parse()
-            .ok()?;
+            .ok();
 
-        Some((dir, count))
-    })
+        match count {
+            Some(count) if toks.next().is_none() => Ok((dir, count as i32)),
+            _ => anyhow::bail!("Line {}: expected a move like 'R 4', got '{l}'", n + 1)
+        }
+    }).collect()
 }
 
 enum Direction {
@@ -113,4 +146,9 @@ impl Direction {
             Direction::Right => (x+1,y),
         }
     }
-}
\ No newline at end of file
+}
+
+/// `size` moves of the rope's head.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1)).map(|_| format!("{} {}\n", rng.pick(&['U', 'D', 'L', 'R']), rng.range(1, 20))).collect()
+}
@@ -1,15 +1,17 @@
-use super::File;
+use crate::{ File, params };
+use crate::rng::Rng;
 use itertools::Itertools;
 
 pub fn star1(file: File) -> Result<i64, anyhow::Error> {
-    let cmds = parse_input(&file.contents);
-    let mut cpu = Machine::new(cmds.collect());
+    let cmds = parse_input(&file.contents)?;
+    let mut cpu = Machine::new(cmds);
+    let cycles: Vec<usize> = params::get_list("2022.day10.cycles", vec![20,60,100,140,180,220])?;
 
     let mut signal_strength = 0;
     while cpu.step() {
         // "during cycle 20" is the same result as "after cycle 19", so we add
         // 1 to the cycle counter to record the result.
-        if [20,60,100,140,180,220].iter().contains(&(cpu.counter() + 1)) {
+        if cycles.iter().contains(&(cpu.counter() + 1)) {
             signal_strength += cpu.x() * (cpu.counter() + 1) as i64;
         }
     }
@@ -17,43 +19,51 @@ pub fn star1(file: File) -> Result<i64, anyhow::Error> {
     Ok(signal_strength)
 }
 
-pub fn star2(file: File) -> Result<&'static str, anyhow::Error> {
-    let cmds = parse_input(&file.contents);
-    let mut cpu = Machine::new(cmds.collect());
+pub fn star2(file: File) -> Result<String, anyhow::Error> {
+    let cmds = parse_input(&file.contents)?;
+    let mut cpu = Machine::new(cmds);
 
-    // during cycle 1 (eg at cycle 0) print '#' to start:
-    print!("#");
+    // during cycle 1 (eg at cycle 0) draw '#' to start:
+    let mut screen = String::from("#");
     let mut x = 1;
+    let mut pixels = 1;
 
-    while cpu.step() {
+    while pixels < 240 && cpu.step() {
         if ((cpu.x()-1)..=(cpu.x()+1)).contains(&x) {
-            print!("#");
+            screen.push('#');
         } else {
-            print!(".");
+            screen.push('.');
         }
 
+        pixels += 1;
         x += 1;
-        if x == 40 {
+        if x == 40 && pixels < 240 {
             x = 0;
-            println!();
+            screen.push('\n');
         }
     }
 
-    Ok("")
+    Ok(screen)
+}
+
+pub fn parse(input: &str) -> Result<(), anyhow::Error> {
+    parse_input(input)?;
+    Ok(())
 }
 
-fn parse_input(input: &str) -> impl Iterator<Item=Instruction> + '_ {
-    let addx_re = regex!("addx (-?[0-9]+)");
-    input.trim().lines().map(|l| l.trim()).filter_map(move |l| {
+fn parse_input(input: &str) -> Result<Vec<Instruction>, anyhow::Error> {
+    let addx_re = regex!("^addx (-?[0-9]+)$");
+    input.lines().map(|l| l.trim()).enumerate().filter(|(_, l)| !l.is_empty()).map(|(n, l)| {
         if let Some(caps) = addx_re.captures(l) {
-            let n = caps.get(1).unwrap().as_str().parse().unwrap();
-            Some(Instruction::Addx(n))
+            // Parsed as an i32 so that adding lots of them up can't overflow an i64.
+            let x: i32 = caps[1].parse().map_err(|e| anyhow::anyhow!("Line {}: {e}", n + 1))?;
+            Ok(Instruction::Addx(x as i64))
         } else if l == "noop" {
-            Some(Instruction::Noop)
+            Ok(Instruction::Noop)
         } else {
-            None
+            anyhow::bail!("Line {}: expected 'noop' or 'addx N', got '{l}'", n + 1)
         }
-    })
+    }).collect()
 }
 
 #[derive(Clone,Copy)]
@@ -133,4 +143,15 @@ impl Machine {
 
         true
     }
-}
\ No newline at end of file
+}
+
+/// `size` instructions.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1)).map(|_| {
+        if rng.chance(0.3) {
+            "noop\n".to_owned()
+        } else {
+            format!("addx {}\n", rng.range(-10, 10))
+        }
+    }).collect()
+}
@@ -1,8 +1,10 @@
+use crate::{ File, params };
 use std::collections::VecDeque;
 
 struct Monkey {
     items: VecDeque<u128>,
-    operation: Box<dyn Fn(u128) -> u128>,
+    /// What happens to an item's worry level, or None if it gets too big to hold.
+    operation: Box<dyn Fn(u128) -> Option<u128>>,
     modulo: u128,
     then: usize,
     otherwise: usize,
@@ -12,56 +14,56 @@ fn monkeys() -> [Monkey; 8] {
     [
         Monkey {
             items: VecDeque::from_iter([99, 67, 92, 61, 83, 64, 98]),
-            operation: Box::new(|n| n * 17),
+            operation: Box::new(|n| n.checked_mul(17)),
             modulo: 3,
             then: 4,
             otherwise: 2,
         },
         Monkey {
             items: VecDeque::from_iter([78, 74, 88, 89, 50]),
-            operation: Box::new(|n| n * 11),
+            operation: Box::new(|n| n.checked_mul(11)),
             modulo: 5,
             then: 3,
             otherwise: 5,
         },
         Monkey {
             items: VecDeque::from_iter([98, 91]),
-            operation: Box::new(|n| n + 4),
+            operation: Box::new(|n| n.checked_add(4)),
             modulo: 2,
             then: 6,
             otherwise: 4,
         },
         Monkey {
             items: VecDeque::from_iter([59, 72, 94, 91, 79, 88, 94, 51]),
-            operation: Box::new(|n| n * n),
+            operation: Box::new(|n| n.checked_mul(n)),
             modulo: 13,
             then: 0,
             otherwise: 5,
         },
         Monkey {
             items: VecDeque::from_iter([95, 72, 78]),
-            operation: Box::new(|n| n + 7),
+            operation: Box::new(|n| n.checked_add(7)),
             modulo: 11,
             then: 7,
             otherwise: 6,
         },
         Monkey {
             items: VecDeque::from_iter([76]),
-            operation: Box::new(|n| n + 8),
+            operation: Box::new(|n| n.checked_add(8)),
             modulo: 17,
             then: 0,
             otherwise: 2,
         },
         Monkey {
             items: VecDeque::from_iter([69, 60, 53, 89, 71, 88]),
-            operation: Box::new(|n| n + 5),
+            operation: Box::new(|n| n.checked_add(5)),
             modulo: 19,
             then: 7,
             otherwise: 1,
         },
         Monkey {
             items: VecDeque::from_iter([72, 54, 63, 80]),
-            operation: Box::new(|n| n + 3),
+            operation: Box::new(|n| n.checked_add(3)),
             modulo: 7,
             then: 1,
             otherwise: 3,
@@ -69,17 +71,19 @@ fn monkeys() -> [Monkey; 8] {
     ]
 }
 
-pub fn star1() -> Result<u128, anyhow::Error> {
+pub fn star1(_file: File) -> Result<u128, anyhow::Error> {
     let mut monkeys = monkeys();
     let mut seen_items = [0u128; 8];
 
     // 20 rounds
-    for _ in 0..20 {
+    let rounds: u64 = params::get("2022.day11.star1_rounds", 20)?;
+    for round in 1..=rounds {
         for idx in 0..monkeys.len() {
             while let Some(item) = monkeys[idx].items.pop_front() {
                 seen_items[idx] += 1;
-                let new_item = (monkeys[idx].operation)(item) / 3;
-                let throw_to = if new_item % monkeys[idx].modulo == 0 {
+                let new_item = (monkeys[idx].operation)(item)
+                    .ok_or_else(|| anyhow::anyhow!("Round {round}: worry level is too big to keep track of"))? / 3;
+                let throw_to = if new_item.is_multiple_of(monkeys[idx].modulo) {
                     monkeys[idx].then
                 } else {
                     monkeys[idx].otherwise
@@ -91,10 +95,10 @@ pub fn star1() -> Result<u128, anyhow::Error> {
 
     // Find most seen items.
     seen_items.sort();
-    Ok(seen_items[6] * seen_items[7])
+    seen_items[6].checked_mul(seen_items[7]).ok_or_else(|| anyhow::anyhow!("Monkey business is too big to work out"))
 }
 
-pub fn star2() -> Result<u128, anyhow::Error> {
+pub fn star2(_file: File) -> Result<u128, anyhow::Error> {
     let mut monkeys = monkeys();
     let mut seen_items = [0u128; 8];
 
@@ -103,14 +107,16 @@ pub fn star2() -> Result<u128, anyhow::Error> {
     // by a modulo that is the multiplication of all of those, I think this means that
     // all of the modulo tests will pan out the same. (I wasn't certain without checking,
     // but it produced the right answer!)
-    let all_mod = monkeys.iter().map(|m| m.modulo).fold(1, |a, b| a * b);
+    let all_mod = monkeys.iter().map(|m| m.modulo).product::<u128>();
 
-    for _ in 0..10_000 {
+    let rounds: u64 = params::get("2022.day11.star2_rounds", 10_000)?;
+    for round in 1..=rounds {
         for idx in 0..monkeys.len() {
             while let Some(item) = monkeys[idx].items.pop_front() {
                 seen_items[idx] += 1;
-                let new_item = (monkeys[idx].operation)(item) % all_mod;
-                let throw_to = if new_item % monkeys[idx].modulo == 0 {
+                let new_item = (monkeys[idx].operation)(item)
+                    .ok_or_else(|| anyhow::anyhow!("Round {round}: worry level is too big to keep track of"))? % all_mod;
+                let throw_to = if new_item.is_multiple_of(monkeys[idx].modulo) {
                     monkeys[idx].then
                 } else {
                     monkeys[idx].otherwise
@@ -122,5 +128,5 @@ pub fn star2() -> Result<u128, anyhow::Error> {
 
     // Find most seen items.
     seen_items.sort();
-    Ok(seen_items[6] * seen_items[7])
+    seen_items[6].checked_mul(seen_items[7]).ok_or_else(|| anyhow::anyhow!("Monkey business is too big to work out"))
 }
\ No newline at end of file
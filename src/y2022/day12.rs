@@ -0,0 +1,176 @@
+use crate::File;
+use crate::rng::Rng;
+#[cfg(feature = "viz")]
+use crate::utils;
+use std::collections::{ HashMap, VecDeque };
+
+struct Map {
+    start: (i32,i32),
+    end: (i32,i32),
+    heights: HashMap<(i32,i32), u32>
+}
+
+pub fn parse(input: &str) -> Result<(), anyhow::Error> {
+    parse_input(input)?;
+    Ok(())
+}
+
+fn parse_input(input: &str) -> anyhow::Result<Map> {
+    let mut start = None;
+    let mut end = None;
+    let mut heights = HashMap::new();
+
+    for (y, l) in input.trim().lines().enumerate() {
+        for (x, mut c) in l.trim().chars().enumerate() {
+            let coords = (x as i32, y as i32);
+            if c == 'S' {
+                start = Some(coords);
+                c = 'a';
+            } else if c == 'E' {
+                end = Some(coords);
+                c = 'z';
+            }
+            heights.insert(coords, c as u32);
+        }
+    }
+
+    Ok(Map {
+        start: start.ok_or_else(|| anyhow::anyhow!("No 'S' start token found"))?,
+        end: end.ok_or_else(|| anyhow::anyhow!("no 'E' end token found"))?,
+        heights
+    })
+}
+
+fn steps_map(start: (i32,i32), heights: &HashMap<(i32,i32), u32>) -> HashMap<(i32,i32), usize> {
+    let mut steps = HashMap::<(i32,i32), usize>::from_iter([(start, 0)]);
+    let mut next = VecDeque::from_iter([start]);
+
+    while let Some((x,y)) = next.pop_front() {
+        let curr_steps = *steps.get(&(x,y)).unwrap();
+        let curr_height = *heights.get(&(x,y)).unwrap();
+
+        let possible_next = [(x-1, y), (x+1, y), (x, y-1), (x, y+1)]
+            .into_iter()
+            .filter(|xy| {
+                matches!(heights.get(xy), Some(h) if *h <= curr_height + 1)
+            });
+
+        for xy in possible_next {
+            let e = steps.entry(xy).or_insert(usize::MAX);
+            if *e > curr_steps + 1 {
+                *e = curr_steps + 1;
+                next.push_back(xy);
+            }
+        }
+    }
+
+    steps
+}
+
+pub fn star1(file: File) -> Result<usize, anyhow::Error> {
+    let Map { start, end, heights } = parse_input(&file.contents)?;
+    let steps = steps_map(start, &heights);
+    steps.get(&end).copied().ok_or_else(|| anyhow::anyhow!("We didn't make it to the end!"))
+}
+
+pub fn star2(file: File) -> Result<usize, anyhow::Error> {
+    let Map { start, end, heights } = parse_input(&file.contents)?;
+    let min_height = heights.get(&start).unwrap();
+
+    let min_steps = heights
+        .iter()
+        .filter(|(_, h)| *h == min_height)
+        .filter_map(|(xy,_)| {
+            let steps = steps_map(*xy, &heights);
+            steps.get(&end).copied()
+        })
+        .min()
+        .ok_or_else(|| anyhow::anyhow!("We didn't make it to the end from anywhere!"))?;
+
+    Ok(min_steps)
+}
+
+/// Star 1 the slow way, to check the real one against.
+pub fn reference1(file: File) -> Result<usize, anyhow::Error> {
+    let map = parse_input(&file.contents)?;
+    relaxed_steps(&[map.start], &map).ok_or_else(|| anyhow::anyhow!("We didn't make it to the end!"))
+}
+
+/// Star 2 the slow way, to check the real one against.
+pub fn reference2(file: File) -> Result<usize, anyhow::Error> {
+    let map = parse_input(&file.contents)?;
+    let lowest: Vec<(i32,i32)> = map.heights.iter().filter(|(_, h)| **h == 'a' as u32).map(|(xy, _)| *xy).collect();
+    relaxed_steps(&lowest, &map).ok_or_else(|| anyhow::anyhow!("We didn't make it to the end!"))
+}
+
+/// Steps to the end from the nearest of `starts`, found by going over every square
+/// again and again, stepping from its neighbours, until nothing gets any closer.
+fn relaxed_steps(starts: &[(i32,i32)], map: &Map) -> Option<usize> {
+    let mut steps: HashMap<(i32,i32), usize> = starts.iter().map(|xy| (*xy, 0)).collect();
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (&(x,y), &h) in &map.heights {
+            let best = [(x-1, y), (x+1, y), (x, y-1), (x, y+1)]
+                .into_iter()
+                .filter(|xy| matches!(map.heights.get(xy), Some(from) if h <= from + 1))
+                .filter_map(|xy| steps.get(&xy).map(|s| s + 1))
+                .min();
+            if let Some(best) = best {
+                if steps.get(&(x,y)).is_none_or(|s| best < *s) {
+                    steps.insert((x,y), best);
+                    changed = true;
+                }
+            }
+        }
+    }
+    steps.get(&map.end).copied()
+}
+
+/// Shade each square by how many steps it takes to get there from the start.
+#[cfg(feature = "viz")]
+pub fn visualise(file: File) -> Result<String, anyhow::Error> {
+    let Map { start, end, heights } = parse_input(&file.contents)?;
+    let steps = steps_map(start, &heights);
+    let max_steps = steps.values().copied().max().unwrap_or(0).max(1);
+
+    let shades: Vec<char> = ",:-=~*%@".chars().collect();
+    let mut cells: HashMap<(i32,i32), char> = heights
+        .keys()
+        .map(|xy| {
+            let c = match steps.get(xy) {
+                Some(n) => shades[n * (shades.len() - 1) / max_steps],
+                None => ' '
+            };
+            (*xy, c)
+        })
+        .collect();
+    cells.insert(start, 'S');
+    cells.insert(end, 'E');
+    Ok(utils::render_grid(&cells, ' '))
+}
+
+/// A heightmap `size` squares wide (at least 26, to make it up to 'z'), with a
+/// climbable path from the start on the left to the end on the right.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let width = size.max(26);
+    let height = (width / 3).max(5);
+    let path_row = rng.below(height);
+    // Heights rise steadily from left to right along the path, and more or less
+    // so everywhere else.
+    let rise = |x: usize| (x * 25 / (width - 1)) as i64;
+
+    let mut out = String::new();
+    for y in 0..height {
+        for x in 0..width {
+            let h = if y == path_row { rise(x) } else { (rise(x) + rng.range(-4, 2)).clamp(0, 25) };
+            out.push(match (x, y) {
+                (0, y) if y == path_row => 'S',
+                (x, y) if x == width - 1 && y == path_row => 'E',
+                _ => (b'a' + h as u8) as char,
+            });
+        }
+        out.push('\n');
+    }
+    out
+}
@@ -1,10 +1,11 @@
 use std::fmt::Write;
 use yap::{ Tokens, IntoTokens };
-use super::File;
+use crate::File;
+use crate::rng::Rng;
 
 pub fn star1(file: File) -> Result<usize, anyhow::Error> {
-    let pairs = parse_input(&file.contents);
-    let in_order = pairs.enumerate().filter_map(|(idx, (a,b))| {
+    let pairs = parse_input(&file.contents)?;
+    let in_order = pairs.into_iter().enumerate().filter_map(|(idx, (a,b))| {
         if a < b {
             Some(idx+1)
         } else {
@@ -15,8 +16,8 @@ pub fn star1(file: File) -> Result<usize, anyhow::Error> {
 }
 
 pub fn star2(file: File) -> Result<usize, anyhow::Error> {
-    let pairs = parse_input(&file.contents);
-    let mut all_packets: Vec<_> = pairs.flat_map(|(a,b)| [a,b]).collect();
+    let pairs = parse_input(&file.contents)?;
+    let mut all_packets: Vec<_> = pairs.into_iter().flat_map(|(a,b)| [a,b]).collect();
 
     // lazy; just parse rather than write out the Item stuff..
     let divider1 = parse_line("[[2]]").unwrap();
@@ -33,7 +34,7 @@ pub fn star2(file: File) -> Result<usize, anyhow::Error> {
     Ok((pos1.unwrap() + 1) * (pos2.unwrap() + 1))
 }
 
-#[derive(Debug, PartialEq, Eq, Ord, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 enum Item {
     List(Vec<Item>),
     Number(u8)
@@ -59,28 +60,37 @@ impl std::fmt::Display for Item {
 
 impl PartialOrd for Item {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Item {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         match (self, other) {
-            (Item::Number(a), Item::Number(b)) => a.partial_cmp(b),
-            (Item::List(a), Item::List(b)) => a.partial_cmp(b),
-            (a @ Item::Number(_), Item::List(b)) => vec![a.clone()].partial_cmp(b),
-            (Item::List(a), b @ Item::Number(_)) => a.partial_cmp(&vec![b.clone()])
+            (Item::Number(a), Item::Number(b)) => a.cmp(b),
+            (Item::List(a), Item::List(b)) => a.cmp(b),
+            (a @ Item::Number(_), Item::List(b)) => std::slice::from_ref(a).cmp(b),
+            (Item::List(a), b @ Item::Number(_)) => a[..].cmp(std::slice::from_ref(b))
         }
     }
 }
 
+/// How deeply lists can nest, so that silly inputs don't overflow the stack.
+const MAX_DEPTH: usize = 100;
+
 fn parse_line(line: &str) -> Option<Item> {
-    fn parse_item(toks: &mut impl Tokens<Item=char>) -> Option<Item> {
+    fn parse_item(toks: &mut impl Tokens<Item=char>, depth: usize) -> Option<Item> {
         yap::one_of!(toks;
-            parse_list(toks).map(Item::List),
+            parse_list(toks, depth).map(Item::List),
             parse_number(toks).map(Item::Number)
         )
     }
-    fn parse_list(toks: &mut impl Tokens<Item=char>) -> Option<Vec<Item>> {
-        if !toks.token('[') {
+    fn parse_list(toks: &mut impl Tokens<Item=char>, depth: usize) -> Option<Vec<Item>> {
+        if depth >= MAX_DEPTH || !toks.token('[') {
             return None
         }
         let items: Vec<Item> = toks
-            .sep_by(|t| parse_item(t), |t| t.token(','))
+            .sep_by(|t| parse_item(t, depth + 1), |t| t.token(','))
             .collect();
         if !toks.token(']') {
             return None
@@ -88,20 +98,47 @@ fn parse_line(line: &str) -> Option<Item> {
         Some(items)
     }
     fn parse_number(toks: &mut impl Tokens<Item=char>) -> Option<u8> {
-        let n: String = toks.tokens_while(|c| c.is_digit(10)).collect();
+        let n: String = toks.tokens_while(|c| c.is_ascii_digit()).collect();
         n.parse().ok()
     }
 
     let mut toks = line.trim().into_tokens();
-    parse_item(&mut toks)
+    let item = parse_item(&mut toks, 0)?;
+    // Nothing should be left over.
+    toks.next().is_none().then_some(item)
+}
+
+pub fn parse(input: &str) -> Result<(), anyhow::Error> {
+    parse_input(input)?;
+    Ok(())
 }
 
-fn parse_input(input: &str) -> impl Iterator<Item=(Item,Item)> + '_ {
-    input.trim().split("\n\n").map(|pair| {
-        let mut it = pair.split('\n').filter_map(parse_line);
-        // blow up if any parsing fails:
-        (it.next().unwrap(), it.next().unwrap())
-    })
+fn parse_input(input: &str) -> Result<Vec<(Item,Item)>, anyhow::Error> {
+    let lines: Vec<(usize, &str)> = input.lines().map(str::trim).enumerate().map(|(n, l)| (n + 1, l)).collect();
+    let packet = |(n, l): (usize, &str)| parse_line(l)
+        .ok_or_else(|| anyhow::anyhow!("Line {n}: couldn't parse packet '{l}'"));
+
+    lines.split(|(_, l)| l.is_empty()).filter(|pair| !pair.is_empty()).map(|pair| {
+        let [a, b] = pair else {
+            anyhow::bail!("Line {}: expected a pair of packets, but got {}", pair[0].0, pair.len())
+        };
+        Ok((packet(*a)?, packet(*b)?))
+    }).collect()
+}
+
+/// `size` pairs of nested packets.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    fn item(rng: &mut Rng, depth: usize) -> Item {
+        if depth > 0 && (depth > 4 || rng.chance(0.5)) {
+            Item::Number(rng.range(0, 10) as u8)
+        } else {
+            Item::List((0..rng.below(5)).map(|_| item(rng, depth + 1)).collect())
+        }
+    }
+    (0..size.max(1))
+        .map(|_| format!("{}\n{}\n", item(rng, 0), item(rng, 0)))
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 #[cfg(test)]
@@ -134,9 +171,7 @@ mod test {
     }
     macro_rules! items {
         ($( $n:expr ),*) => {{
-            #[allow(unused_mut)]
-            let mut items: Vec<Item> = Vec::new();
-            $( items.push($n.into_item()); )*
+            let items: Vec<Item> = vec![$( $n.into_item() ),*];
             items.into_item()
         }}
     }
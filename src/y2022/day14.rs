@@ -0,0 +1,197 @@
+use crate::{ File, params };
+use crate::rng::Rng;
+#[cfg(feature = "viz")]
+use crate::utils;
+use std::collections::HashSet;
+#[cfg(feature = "viz")]
+use std::collections::HashMap;
+
+pub fn star1(file: File) -> Result<usize, anyhow::Error> {
+    let source = source()?;
+    let walls = parse_walls(&file.contents, source)?;
+
+    let mut sim = Simulation::new(walls, source);
+    while sim.step() {}
+    Ok(sim.settled_sand.len())
+}
+
+pub fn star2(file: File) -> Result<usize, anyhow::Error> {
+    let source = source()?;
+    let mut walls = parse_walls(&file.contents, source)?;
+
+    // Add a floor wide enough to catch everything; the sand can't pile up any
+    // wider than it is tall.
+    let floor_y = lowest_point(&walls) + 2;
+    let width = floor_y - source.1 + 1;
+    for xy in iter_line((source.0 - width, floor_y), (source.0 + width, floor_y)) {
+        walls.insert(xy);
+    }
+
+    let mut sim = Simulation::new(walls, source);
+    while sim.step() {
+        if sim.settled_sand.contains(&source) {
+            break
+        }
+    }
+    Ok(sim.settled_sand.len())
+}
+
+/// Draw the cave once sand starts falling into the abyss.
+#[cfg(feature = "viz")]
+pub fn visualise(file: File) -> Result<String, anyhow::Error> {
+    let source = source()?;
+    let walls = parse_walls(&file.contents, source)?;
+
+    let mut sim = Simulation::new(walls, source);
+    while sim.step() {}
+
+    let mut cells: HashMap<(i32,i32), char> = HashMap::new();
+    cells.insert(source, '+');
+    cells.extend(sim.walls.iter().map(|xy| (*xy, '#')));
+    cells.extend(sim.settled_sand.iter().map(|xy| (*xy, 'o')));
+    Ok(utils::render_grid(&cells, '.'))
+}
+
+/// Where the sand falls from.
+fn source() -> Result<(i32,i32), anyhow::Error> {
+    Ok((params::get("2022.day14.source_x", 500)?, params::get("2022.day14.source_y", 0)?))
+}
+
+struct Simulation {
+    walls: Set,
+    source: (i32,i32),
+    moving_sand: (i32,i32),
+    settled_sand: Set,
+    lowest_wall_y: i32,
+}
+
+impl Simulation {
+    fn new(walls: Set, source: (i32,i32)) -> Self {
+        // set the low point below anything else that could happen (including
+        // the floor introduced in step 2):
+        let lowest_wall_y = lowest_point(&walls) + 10;
+        Simulation {
+            walls,
+            source,
+            moving_sand: source,
+            settled_sand: Set::new(),
+            lowest_wall_y
+        }
+    }
+    fn step(&mut self) -> bool {
+        let (x,y) = self.moving_sand;
+
+        if y > self.lowest_wall_y {
+            return false;
+        } else if !self.blocked((x, y+1)) {
+            self.moving_sand = (x, y+1)
+        } else if !self.blocked((x-1, y+1)) {
+            self.moving_sand = (x-1, y+1);
+        } else if !self.blocked((x+1, y+1)) {
+            self.moving_sand = (x+1, y+1)
+        } else {
+            self.settled_sand.insert((x,y));
+            // Nothing more can fall in once the sand has piled up to the source.
+            if (x,y) == self.source {
+                return false;
+            }
+            self.moving_sand = self.source;
+        }
+        true
+    }
+    fn blocked(&self, xy: (i32,i32)) -> bool {
+        self.walls.contains(&xy) || self.settled_sand.contains(&xy)
+    }
+}
+
+fn lowest_point(map: &Set) -> i32 {
+    map.iter().map(|(_,y)| *y).max().unwrap_or(0)
+}
+
+type Set = HashSet<(i32,i32)>;
+
+/// How far from the source rock can be, to keep the simulation to a sensible size.
+const MAX_DISTANCE: i32 = 1000;
+
+pub fn parse(input: &str) -> Result<(), anyhow::Error> {
+    parse_walls(input, source()?)?;
+    Ok(())
+}
+
+fn parse_walls(input: &str, source: (i32,i32)) -> Result<Set, anyhow::Error> {
+    let mut map = HashSet::new();
+    let coords = regex!("^([0-9]+),([0-9]+)$");
+    for (n, line) in input.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+        let points = line.split("->").map(|point| {
+            let caps = coords.captures(point.trim())
+                .ok_or_else(|| anyhow::anyhow!("Line {}: expected a point like '498,4', got '{}'", n + 1, point.trim()))?;
+            let xy: (i32,i32) = (caps[1].parse()?, caps[2].parse()?);
+            if (xy.0 - source.0).abs() > MAX_DISTANCE || (xy.1 - source.1).abs() > MAX_DISTANCE {
+                anyhow::bail!("Line {}: {},{} is too far from the source (over {MAX_DISTANCE})", n + 1, xy.0, xy.1);
+            }
+            Ok(xy)
+        }).collect::<Result<Vec<_>, anyhow::Error>>()?;
+
+        for (a, b) in points.iter().zip(points.iter().skip(1)) {
+            if a.0 != b.0 && a.1 != b.1 {
+                anyhow::bail!("Line {}: {},{} -> {},{} isn't a straight line", n + 1, a.0, a.1, b.0, b.1);
+            }
+            map.extend(iter_line(*a, *b));
+        }
+        // A path can also be a single point.
+        map.extend(points.first());
+    }
+    Ok(map)
+}
+
+fn iter_line(mut curr: (i32,i32), end: (i32,i32)) -> impl Iterator<Item=(i32,i32)> {
+    let mut finished = false;
+    std::iter::from_fn(move || {
+        if finished {
+            return None;
+        }
+
+        let out = curr;
+
+        if curr == end {
+            finished = true;
+        } else if curr.0 == end.0 {
+            // change y
+            if curr.1 < end.1 {
+                curr = (curr.0, curr.1 + 1)
+            } else {
+                curr = (curr.0, curr.1 - 1)
+            }
+        } else {
+            // change x
+            if curr.0 < end.0 {
+                curr = (curr.0 + 1, curr.1)
+            } else {
+                curr = (curr.0 - 1, curr.1)
+            }
+        }
+
+        Some(out)
+    })
+}
+
+/// `size` paths of rock below the source of the sand.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let (source_x, source_y) = source().unwrap_or((500, 0));
+    let depth = (size as i32).clamp(10, 150);
+    (0..size.max(1)).map(|_| {
+        let mut x = source_x + rng.range(-depth as i64, depth as i64) as i32;
+        let mut y = source_y + 2 + rng.range(0, depth as i64) as i32;
+        let mut points = vec![format!("{x},{y}")];
+        for n in 0..rng.range(1, 5) {
+            // Alternate horizontal and vertical lines, as the rocks tend to.
+            if n % 2 == 0 {
+                x += rng.range(-6, 6) as i32;
+            } else {
+                y = (y + rng.range(-6, 6) as i32).max(source_y + 2);
+            }
+            points.push(format!("{x},{y}"));
+        }
+        points.join(" -> ") + "\n"
+    }).collect()
+}
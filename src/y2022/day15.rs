@@ -0,0 +1,230 @@
+use crate::{ File, params };
+use crate::rng::Rng;
+use std::collections::HashSet;
+
+pub fn star1(file: File) -> Result<usize, anyhow::Error> {
+    let sensors = Sensor::from_lines(&file.contents)?;
+
+    let row: i64 = params::get("2022.day15.row", 2_000_000)?;
+    let mut taken_ranges: Vec<(i64,i64)> = taken_ranges_in_row(&sensors, row).collect();
+    taken_ranges.sort();
+
+    // Merge any overlapping ranges and then count the sizes of the disjoint ones.
+    let mut merged: Vec<(i64,i64)> = Vec::new();
+    for (start, end) in taken_ranges {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 + 1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    let taken: i64 = merged.iter().map(|(start, end)| end - start + 1).sum();
+
+    // dont count any beacons in the locations that are covered (which they all
+    // are, by their own sensor):
+    let beacons_in_row: HashSet<i64> = sensors.iter().filter(|s| s.beacon_y == row).map(|s| s.beacon_x).collect();
+
+    Ok(taken as usize - beacons_in_row.len())
+}
+
+pub fn star2(file: File) -> Result<i64, anyhow::Error> {
+    let sensors = Sensor::from_lines(&file.contents)?;
+
+    let max: i64 = params::get("2022.day15.max", 4_000_000)?;
+    for y in 0 ..= max {
+        if let Some(x) = find_free_spot_in_row(&sensors, y, max) {
+            return tuning_frequency(x, y)
+        }
+    }
+
+    anyhow::bail!("Could not find any free location for the beacon");
+}
+
+fn find_free_spot_in_row(sensors: &[Sensor], row: i64, max: i64) -> Option<i64> {
+    let mut taken_ranges: Vec<(i64,i64)> = taken_ranges_in_row(sensors, row).collect();
+    taken_ranges.sort_by_key(|(start,_end)| *start);
+
+    // Assuming max 1 free spot in range, we just work through our claimed sensor spots
+    // and see whether any of the possible X values before `max` are free, skipping over
+    // any values that sensors can see.
+    let mut x = 0;
+    for (start, end) in taken_ranges {
+        if start > x || x > max {
+            break
+        }
+        x = x.max(end + 1);
+    }
+    (x <= max).then_some(x)
+}
+
+fn taken_ranges_in_row(sensors: &[Sensor], row: i64) -> impl Iterator<Item=(i64,i64)> + '_ {
+    sensors.iter().filter_map(move |sensor| {
+        // Rows can be anywhere, so this might not fit in an i64.
+        let sensor_distance = sensor.y.abs_diff(row);
+        let sensor_radius = sensor.radius();
+        if sensor_distance > sensor_radius as u64 {
+            None
+        } else {
+            let radius_at_y = sensor_radius - sensor_distance as i64;
+            let range = (sensor.x - radius_at_y, sensor.x + radius_at_y);
+            Some(range)
+        }
+    })
+}
+
+/// Star 1 the slow way, checking each spot along the row against every sensor.
+pub fn reference1(file: File) -> Result<usize, anyhow::Error> {
+    let sensors: Vec<Sensor> = Sensor::from_lines(&file.contents)?;
+    let row: i64 = params::get("2022.day15.row", 2_000_000)?;
+
+    let left = sensors.iter().map(|s| s.x - s.radius()).min().unwrap_or(0);
+    let right = sensors.iter().map(|s| s.x + s.radius()).max().unwrap_or(-1);
+    let count = (left ..= right)
+        .filter(|x| sensors.iter().any(|s| s.covers(*x, row)))
+        .filter(|x| !sensors.iter().any(|s| (s.beacon_x, s.beacon_y) == (*x, row)))
+        .count();
+    Ok(count)
+}
+
+/// Star 2 the slow way, checking every spot in the search area against every
+/// sensor. Only any use with a small search area.
+pub fn reference2(file: File) -> Result<i64, anyhow::Error> {
+    let sensors: Vec<Sensor> = Sensor::from_lines(&file.contents)?;
+    let max: i64 = params::get("2022.day15.max", 4_000_000)?;
+
+    for y in 0 ..= max {
+        for x in 0 ..= max {
+            if !sensors.iter().any(|s| s.covers(x, y)) {
+                return tuning_frequency(x, y)
+            }
+        }
+    }
+    anyhow::bail!("Could not find any free location for the beacon");
+}
+
+/// The answer for a free spot, which needs checking as the search area can be huge.
+fn tuning_frequency(x: i64, y: i64) -> Result<i64, anyhow::Error> {
+    x.checked_mul(4_000_000)
+        .and_then(|f| f.checked_add(y))
+        .ok_or_else(|| anyhow::anyhow!("The tuning frequency of ({x},{y}) is too big to work out"))
+}
+
+pub fn parse(input: &str) -> Result<(), anyhow::Error> {
+    Sensor::from_lines(input)?;
+    Ok(())
+}
+
+struct Sensor {
+    x: i64,
+    y: i64,
+    beacon_x: i64,
+    beacon_y: i64
+}
+
+impl Sensor {
+    fn radius(&self) -> i64 {
+        (self.x - self.beacon_x).abs() + (self.y - self.beacon_y).abs()
+    }
+    fn covers(&self, x: i64, y: i64) -> bool {
+        self.x.abs_diff(x).saturating_add(self.y.abs_diff(y)) <= self.radius() as u64
+    }
+    fn from_lines(input: &str) -> Result<Vec<Sensor>, anyhow::Error> {
+        input.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()).map(|(n, l)| {
+            Sensor::from_line(l).map_err(|e| anyhow::anyhow!("Line {}: {e}", n + 1))
+        }).collect()
+    }
+    fn from_line(l: &str) -> Result<Sensor, anyhow::Error> {
+        let re = regex!("^Sensor at x=(-?[0-9]+), y=(-?[0-9]+): closest beacon is at x=(-?[0-9]+), y=(-?[0-9]+)$");
+        let caps = re.captures(l.trim())
+            .ok_or_else(|| anyhow::anyhow!("expected 'Sensor at x=N, y=N: closest beacon is at x=N, y=N', got '{}'", l.trim()))?;
+
+        // Parsed as i32s so that there's plenty of room to work with them as i64s.
+        let get = |n: usize| caps[n].parse::<i32>().map(i64::from);
+
+        Ok(Sensor {
+            x: get(1)?,
+            y: get(2)?,
+            beacon_x: get(3)?,
+            beacon_y: get(4)?
+        })
+    }
+}
+
+/// Sensors covering everywhere in the search area but one spot, plus `size`
+/// more scattered about. Uses the row and search area from the params.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    // Kept small enough that everything placed fits in the i32s that sensors are read as.
+    let max: i64 = params::get("2022.day15.max", 4_000_000).unwrap_or(4_000_000).clamp(1, i32::MAX as i64 / 8);
+    let (gx, gy) = (rng.range(0, max), rng.range(0, max));
+    let mut sensors = Vec::new();
+
+    // Four sensors whose ranges are cones pointing at the gap from each side and
+    // which, between them, cover everything that isn't diagonal from it..
+    let (left, right, up, down) = (gx + 1, max - gx + 1, gy + 1, max - gy + 1);
+    sensors.push(((gx - left, gy), left - 1));
+    sensors.push(((gx + right, gy), right - 1));
+    sensors.push(((gx, gy - up), up - 1));
+    sensors.push(((gx, gy + down), down - 1));
+    // ..and four more over the diagonals, each reaching just short of the gap.
+    for (dx, dy) in [(-1, -1), (1, -1), (-1, 1), (1, 1)] {
+        let reach = max + 1;
+        sensors.push(((gx + dx * reach, gy + dy * reach), 2 * reach - 1));
+    }
+    // Then some more that get close to the gap without covering it.
+    for _ in 0..size {
+        let (x, y) = (rng.range(0, max), rng.range(0, max));
+        let distance = (x - gx).abs() + (y - gy).abs();
+        if distance > 0 {
+            sensors.push(((x, y), rng.range(0, distance - 1)));
+        }
+    }
+
+    rng.shuffle(&mut sensors);
+    sensors.into_iter().map(|((x, y), radius)| {
+        // The closest beacon is somewhere on the edge of the sensor's range.
+        let dx = rng.range(-radius, radius);
+        let dy = (radius - dx.abs()) * if rng.chance(0.5) { 1 } else { -1 };
+        format!("Sensor at x={x}, y={y}: closest beacon is at x={}, y={}\n", x + dx, y + dy)
+    }).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = "
+            Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+            Sensor at x=9, y=16: closest beacon is at x=10, y=16
+            Sensor at x=13, y=2: closest beacon is at x=15, y=3
+            Sensor at x=12, y=14: closest beacon is at x=10, y=16
+            Sensor at x=10, y=20: closest beacon is at x=10, y=16
+            Sensor at x=14, y=17: closest beacon is at x=10, y=16
+            Sensor at x=8, y=7: closest beacon is at x=2, y=10
+            Sensor at x=2, y=0: closest beacon is at x=2, y=10
+            Sensor at x=0, y=11: closest beacon is at x=2, y=10
+            Sensor at x=20, y=14: closest beacon is at x=25, y=17
+            Sensor at x=17, y=20: closest beacon is at x=21, y=22
+            Sensor at x=16, y=7: closest beacon is at x=15, y=3
+            Sensor at x=14, y=3: closest beacon is at x=15, y=3
+            Sensor at x=20, y=1: closest beacon is at x=15, y=3
+        ";
+
+        params::with(&[("2022.day15.row", "10"), ("2022.day15.max", "20")], || {
+            assert_eq!(star1(File { contents: input.to_string() }).unwrap(), 26);
+            assert_eq!(star2(File { contents: input.to_string() }).unwrap(), 56000011);
+        });
+    }
+
+    #[test]
+    fn rows_far_away() {
+        let input = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15\n";
+        for row in [i64::MIN, i64::MAX] {
+            params::with(&[("2022.day15.row", &row.to_string())], || {
+                assert_eq!(star1(File { contents: input.to_string() }).unwrap(), 0);
+                assert_eq!(reference1(File { contents: input.to_string() }).unwrap(), 0);
+            });
+        }
+        assert!(tuning_frequency(i64::MAX / 2, 0).is_err());
+    }
+}
@@ -0,0 +1,175 @@
+use crate::{ File, params };
+use crate::rng::Rng;
+use std::collections::{ HashMap, HashSet, VecDeque };
+
+pub fn star1(file: File) -> Result<usize, anyhow::Error> {
+    let start: String = params::get("2022.day16.start", "AA".to_owned())?;
+    let minutes: usize = params::get("2022.day16.minutes", 30)?;
+    if minutes == 0 {
+        anyhow::bail!("Need at least a minute to release any pressure");
+    }
+    let map = parse_input(&file.contents)?;
+    if !map.contains_key(start.as_str()) {
+        anyhow::bail!("No valve called {start} to start from");
+    }
+    let openable_valves = map.values().filter(|v| v.rate > 0).count();
+
+    struct SearchItem<'a> {
+        valve: &'a str,
+        released: usize,
+        time_left: usize,
+        open: HashSet<&'a str>,
+        last: Option<&'a str>
+    }
+
+    // the search queue.
+    let mut current = VecDeque::from_iter([
+        SearchItem { valve: &start, released: 0, time_left: minutes, open: HashSet::new(), last: None }
+    ]);
+    // the best result we've found so far.
+    let mut best_released = 0;
+
+    while let Some(curr) = current.pop_back() {
+        let valve = map.get(curr.valve).unwrap();
+        let time_left = curr.time_left - 1;
+
+        // No time or all valves open; nothing more we can do so end.
+        if time_left == 0 || curr.open.len() == openable_valves {
+            if curr.released > best_released {
+                best_released = curr.released;
+            }
+            continue
+        }
+
+        // One move for each connection.
+        for conn in &valve.connections {
+            if let Some(v) = curr.last {
+                if v == *conn {
+                    continue
+                }
+            }
+            current.push_back(SearchItem {
+                valve: conn,
+                released: curr.released,
+                time_left,
+                open: curr.open.clone(),
+                last: Some(curr.valve)
+            })
+        }
+
+        // One move to turn the valve on in current location.
+        if valve.rate > 0 && !curr.open.contains(curr.valve) {
+            let mut open = curr.open;
+            open.insert(curr.valve);
+
+            current.push_back(SearchItem {
+                valve: curr.valve,
+                released: curr.released + (time_left * valve.rate),
+                time_left,
+                open,
+                last: None
+            });
+        }
+    }
+
+    Ok(best_released)
+}
+
+pub fn star2(_file: File) -> Result<&'static str, anyhow::Error> {
+    Ok("This is about where I can't be bothered any more :)")
+}
+
+struct Valve<'a> {
+    rate: usize,
+    connections: Vec<&'a str>
+}
+
+pub fn parse(input: &str) -> Result<(), anyhow::Error> {
+    parse_input(input)?;
+    Ok(())
+}
+
+fn parse_input(input: &str) -> Result<HashMap<&str, Valve<'_>>, anyhow::Error> {
+    let line_re = regex!("^Valve ([A-Z]{2}) has flow rate=([0-9]+); tunnels? leads? to valves? ([A-Z]{2}(, [A-Z]{2})*)$");
+
+    let mut valves = Vec::new();
+    for (n, l) in input.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+        let caps = line_re.captures(l.trim())
+            .ok_or_else(|| anyhow::anyhow!("Line {}: expected 'Valve XX has flow rate=N; tunnels lead to valves YY, ZZ', got '{}'", n + 1, l.trim()))?;
+        let name = caps.get(1).map_or("", |c| c.as_str());
+        // Not usize, so that there's no overflowing when multiplying by the minutes.
+        let rate: u32 = caps[2].parse().map_err(|e| anyhow::anyhow!("Line {}: {e}", n + 1))?;
+        let connections: Vec<&str> = caps.get(3).map_or("", |c| c.as_str()).split(", ").collect();
+        valves.push((n + 1, name, Valve { rate: rate as usize, connections }));
+    }
+
+    // Every tunnel should lead somewhere we know about.
+    let names: HashSet<&str> = valves.iter().map(|(_, name, _)| *name).collect();
+    for (line, _, valve) in &valves {
+        if let Some(to) = valve.connections.iter().find(|to| !names.contains(*to)) {
+            anyhow::bail!("Line {line}: there's no valve {to} for the tunnel to lead to");
+        }
+    }
+    Ok(valves.into_iter().map(|(_, name, valve)| (name, valve)).collect())
+}
+
+/// `size` valves joined up by tunnels, about a third of which release pressure.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let start: String = params::get("2022.day16.start", "AA".to_owned()).unwrap_or_else(|_| "AA".to_owned());
+    let mut names = vec![start];
+    while names.len() < size.max(2) {
+        let name: String = (0..2).map(|_| (b'A' + rng.below(26) as u8) as char).collect();
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+
+    // Join each valve to one before it so that they're all reachable, and then
+    // add a few more tunnels. Tunnels go both ways.
+    let mut tunnels: Vec<Vec<usize>> = vec![Vec::new(); names.len()];
+    let mut join = |a: usize, b: usize| {
+        if a != b && !tunnels[a].contains(&b) {
+            tunnels[a].push(b);
+            tunnels[b].push(a);
+        }
+    };
+    for n in 1..names.len() {
+        join(n, rng.below(n));
+    }
+    for _ in 0..names.len() / 2 {
+        join(rng.below(names.len()), rng.below(names.len()));
+    }
+
+    names.iter().enumerate().map(|(n, name)| {
+        let rate = if n > 0 && rng.chance(0.35) { rng.range(1, 25) } else { 0 };
+        let to: Vec<&str> = tunnels[n].iter().map(|t| names[*t].as_str()).collect();
+        if to.len() == 1 {
+            format!("Valve {name} has flow rate={rate}; tunnel leads to valve {}\n", to[0])
+        } else {
+            format!("Valve {name} has flow rate={rate}; tunnels lead to valves {}\n", to.join(", "))
+        }
+    }).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = "
+            Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+            Valve BB has flow rate=13; tunnels lead to valves CC, AA
+            Valve CC has flow rate=2; tunnels lead to valves DD, BB
+            Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+            Valve EE has flow rate=3; tunnels lead to valves FF, DD
+            Valve FF has flow rate=0; tunnels lead to valves EE, GG
+            Valve GG has flow rate=0; tunnels lead to valves FF, HH
+            Valve HH has flow rate=22; tunnel leads to valve GG
+            Valve II has flow rate=0; tunnels lead to valves AA, JJ
+            Valve JJ has flow rate=21; tunnel leads to valve II
+        ";
+
+        assert_eq!(star1(File { contents: input.to_string() }).unwrap(), 1651);
+    }
+}
\ No newline at end of file
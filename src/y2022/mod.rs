@@ -0,0 +1,190 @@
+#[cfg(feature = "day01")]
+mod day01;
+#[cfg(feature = "day02")]
+mod day02;
+#[cfg(feature = "day03")]
+mod day03;
+#[cfg(feature = "day04")]
+mod day04;
+#[cfg(feature = "day05")]
+mod day05;
+#[cfg(feature = "day06")]
+mod day06;
+#[cfg(feature = "day07")]
+mod day07;
+#[cfg(feature = "day08")]
+mod day08;
+#[cfg(feature = "day09")]
+mod day09;
+#[cfg(feature = "day10")]
+mod day10;
+#[cfg(feature = "day11")]
+mod day11;
+#[cfg(feature = "day12")]
+mod day12;
+#[cfg(feature = "day13")]
+mod day13;
+#[cfg(feature = "day14")]
+mod day14;
+#[cfg(feature = "day15")]
+mod day15;
+#[cfg(feature = "day16")]
+mod day16;
+
+use crate::solution::Solution;
+
+// Each day is only pushed if it's been built in (see the day features).
+#[allow(unused_mut, clippy::vec_init_then_push)]
+pub fn solutions() -> Vec<Solution> {
+    let mut solutions = Vec::new();
+    #[cfg(feature = "day01")]
+    solutions.push(Solution {
+        generate: Some(day01::generate),
+        reference1: Some(|file| day01::reference1(file).map(|a| a.to_string())),
+        reference2: Some(|file| day01::reference2(file).map(|a| a.to_string())),
+        stream1: Some(|input| day01::stream1(input).map(|a| a.to_string())),
+        stream2: Some(|input| day01::stream2(input).map(|a| a.to_string())),
+        extras: day01::EXTRAS,
+        ..solution!(2022, 1, day01, "Calorie Counting", "Summing groups of numbers and then summing best 3 groups.")
+    });
+    #[cfg(feature = "day02")]
+    solutions.push(Solution {
+        parse: Some(day02::parse),
+        generate: Some(day02::generate),
+        extras: day02::EXTRAS,
+        ..solution!(2022, 2, day02, "Rock Paper Scissors", "Using set moves to win.")
+    });
+    #[cfg(feature = "day03")]
+    solutions.push(Solution {
+        generate: Some(day03::generate),
+        extras: day03::EXTRAS,
+        ..solution!(2022, 3, day03, "Rucksack Reorganization", "Find the duplicate letter in each half of a string (packing items into compartments), and then in each group of 3 strings.")
+    });
+    #[cfg(feature = "day04")]
+    solutions.push(Solution {
+        parse: Some(day04::parse),
+        generate: Some(day04::generate),
+        extras: day04::EXTRAS,
+        ..solution!(2022, 4, day04, "Camp Cleanup", "Find overlapping work schedules.")
+    });
+    #[cfg(feature = "day05")]
+    solutions.push(Solution {
+        parse: Some(day05::parse),
+        generate: Some(day05::generate),
+        ..solution!(2022, 5, day05, "Supply Stacks", "Rearranging crates stacked on top of eachother by following instructions.")
+    });
+    #[cfg(feature = "day06")]
+    solutions.push(Solution {
+        generate: Some(day06::generate),
+        ..solution!(2022, 6, day06, "Tuning Trouble", "Find first 4 or 14 non-repeating letters.")
+    });
+    #[cfg(feature = "day07")]
+    solutions.push(Solution {
+        parse: Some(day07::parse),
+        generate: Some(day07::generate),
+        ..solution!(2022, 7, day07, "No Space Left On Device", "Parse terminal commands to worrk out file structure and sizes of things.")
+    });
+    #[cfg(feature = "day08")]
+    solutions.push(Solution {
+        parse: Some(day08::parse),
+        generate: Some(day08::generate),
+        ..solution!(2022, 8, day08, "Treetop Tree House", "Counting visible trees in a grid based on their height.")
+    });
+    #[cfg(feature = "day09")]
+    solutions.push(Solution {
+        parse: Some(day09::parse),
+        #[cfg(feature = "viz")]
+        visualise: Some(day09::visualise),
+        generate: Some(day09::generate),
+        ..solution!(2022, 9, day09, "Rope Bridge", "A rope tail following a head around.")
+    });
+    #[cfg(feature = "day10")]
+    solutions.push(Solution {
+        parse: Some(day10::parse),
+        #[cfg(feature = "viz")]
+        visualise: Some(day10::star2),
+        generate: Some(day10::generate),
+        ..solution!(2022, 10, day10, "Cathode-Ray Tube", "Basic assembly; noop & addx. Check value of X at cycles. Print a message.")
+    });
+    #[cfg(feature = "day11")]
+    solutions.push(Solution {
+        input: false,
+        ..solution!(2022, 11, day11, "Monkey in the Middle", "Monkeys throwing items to eachother with rules. Hardcoded, so no file input. Couldn't be bothered to parse it. Liked the part 2.")
+    });
+    #[cfg(feature = "day12")]
+    solutions.push(Solution {
+        parse: Some(day12::parse),
+        #[cfg(feature = "viz")]
+        visualise: Some(day12::visualise),
+        generate: Some(day12::generate),
+        reference1: Some(|file| day12::reference1(file).map(|a| a.to_string())),
+        reference2: Some(|file| day12::reference2(file).map(|a| a.to_string())),
+        ..solution!(2022, 12, day12, "Hill Climbing Algorithm", "Find the shortest path(s) up a hill.")
+    });
+    #[cfg(feature = "day13")]
+    solutions.push(Solution {
+        parse: Some(day13::parse),
+        generate: Some(day13::generate),
+        ..solution!(2022, 13, day13, "Distress Signal", "Compare pairs of items/lists of items, and then sort them.")
+    });
+    #[cfg(feature = "day14")]
+    solutions.push(Solution {
+        parse: Some(day14::parse),
+        #[cfg(feature = "viz")]
+        visualise: Some(day14::visualise),
+        generate: Some(day14::generate),
+        ..solution!(2022, 14, day14, "Regolith Reservoir", "Simulate falling sand and see how much settles.")
+    });
+    #[cfg(feature = "day15")]
+    solutions.push(Solution {
+        parse: Some(day15::parse),
+        generate: Some(day15::generate),
+        reference1: Some(|file| day15::reference1(file).map(|a| a.to_string())),
+        reference2: Some(|file| day15::reference2(file).map(|a| a.to_string())),
+        ..solution!(2022, 15, day15, "Beacon Exclusion Zone", "Sensors and beacons; which square is the actual beacon on?")
+    });
+    #[cfg(feature = "day16")]
+    solutions.push(Solution {
+        parse: Some(day16::parse),
+        generate: Some(day16::generate),
+        ..solution!(2022, 16, day16, "Proboscidea Volcanium", "Searching tunnels and release valves to find best order to release most pressure.")
+    });
+    solutions
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{ differential, File, Star, params };
+
+    #[test]
+    fn generated_inputs_solve() {
+        // Keep day 15's search area small so that this doesn't take forever.
+        params::with(&[("2022.day15.max", "200"), ("2022.day15.row", "100")], || {
+            for solution in super::solutions().into_iter().filter(|s| s.generate.is_some()) {
+                // Day 16 searches every route, which gets slow fast as valves are added.
+                let size = if solution.day == 16 { 4 } else { 8 };
+                for seed in 0..5 {
+                    let input = solution.generate(seed, size).unwrap();
+                    assert_eq!(input, solution.generate(seed, size).unwrap(), "day {} isn't repeatable", solution.day);
+                    for star in [Star::One, Star::Two] {
+                        let res = solution.run(star, File { contents: input.clone() });
+                        assert!(res.is_ok(), "day {} star {} seed {seed}: {res:?}\n{input}", solution.day, star.level());
+                    }
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn references_agree() {
+        params::with(&[("2022.day15.max", "50"), ("2022.day15.row", "25")], || {
+            for solution in super::solutions().into_iter().filter(|s| s.generate.is_some()) {
+                for star in [Star::One, Star::Two].into_iter().filter(|star| solution.reference(*star).is_some()) {
+                    if let Some(mismatch) = differential::check(&solution, star, 0..50, 15).unwrap() {
+                        panic!("{mismatch}");
+                    }
+                }
+            }
+        });
+    }
+}